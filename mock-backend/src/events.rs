@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use wordle_protocol::game_model::LeaderboardEvent;
+
+use crate::store::hide_letters;
+
+/// An open connection and who is watching through it
+struct Stream {
+    writer: Box<dyn Write + Send>,
+    viewer: String,
+    /// Guesses reach this viewer as colors only, see `Store::manage`
+    redacted: bool,
+}
+
+/// Open `/manage/{id}/events` connections, written to directly since tiny_http has no notion of
+/// a streamed response
+#[derive(Default)]
+pub struct EventStreams {
+    streams: HashMap<String, Vec<Stream>>,
+}
+
+impl EventStreams {
+    /// Sends the response head and `snapshot` down `writer`, then keeps it for `publish`
+    pub fn open(
+        &mut self,
+        game_id: &str,
+        viewer: &str,
+        redacted: bool,
+        mut writer: Box<dyn Write + Send>,
+        snapshot: LeaderboardEvent,
+    ) {
+        let head = "HTTP/1.1 200 OK\r\n\
+                    Content-Type: text/event-stream\r\n\
+                    Cache-Control: no-cache\r\n\
+                    Connection: close\r\n\r\n";
+        if writer.write_all(head.as_bytes()).is_ok() && send(&mut writer, &snapshot) {
+            self.streams
+                .entry(game_id.to_owned())
+                .or_default()
+                .push(Stream {
+                    writer,
+                    viewer: viewer.to_owned(),
+                    redacted,
+                });
+        }
+    }
+
+    /// Streams that fail to write are assumed closed by the client and dropped
+    pub fn publish(&mut self, game_id: &str, event: LeaderboardEvent) {
+        let redacted = redact(event.clone());
+        if let Some(streams) = self.streams.get_mut(game_id) {
+            streams.retain_mut(|stream| {
+                let event = if stream.redacted { &redacted } else { &event };
+                send(&mut stream.writer, event)
+            });
+        }
+    }
+
+    /// Stops redacting `viewer`'s streams, starting them over from the unredacted `snapshot`
+    pub fn unredact(&mut self, game_id: &str, viewer: &str, snapshot: LeaderboardEvent) {
+        if let Some(streams) = self.streams.get_mut(game_id) {
+            streams.retain_mut(|stream| {
+                if stream.viewer != viewer || !stream.redacted {
+                    return true;
+                }
+                stream.redacted = false;
+                send(&mut stream.writer, &snapshot)
+            });
+        }
+    }
+}
+
+fn redact(mut event: LeaderboardEvent) -> LeaderboardEvent {
+    match &mut event {
+        LeaderboardEvent::Snapshot(game) => game
+            .players
+            .iter_mut()
+            .flat_map(|player| player.guesses.iter_mut())
+            .for_each(hide_letters),
+        LeaderboardEvent::PlayerJoined(player) => player.guesses.iter_mut().for_each(hide_letters),
+        LeaderboardEvent::Guess { guess, .. } => hide_letters(guess),
+    }
+    event
+}
+
+fn send(writer: &mut Box<dyn Write + Send>, event: &LeaderboardEvent) -> bool {
+    let data = serde_json::to_string(event).unwrap();
+    write!(writer, "data: {data}\n\n")
+        .and_then(|_| writer.flush())
+        .is_ok()
+}
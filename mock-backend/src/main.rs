@@ -0,0 +1,225 @@
+//! In-memory stand-in for wordle-backend so the frontend can be developed offline.
+//!
+//! Serves the routes documented in `wordle-protocol` without the `/api/v1` prefix, which is
+//! what `trunk serve` forwards to `localhost:8000` (see `Trunk.toml`).
+
+use std::io::Cursor;
+
+use serde::{de::DeserializeOwned, Serialize};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+mod events;
+mod store;
+
+use store::{Store, StoreError};
+use wordle_protocol::{
+    game_model::{
+        CreateGameRequest, CreateGameResponse, JoinGameRequest, ManageGameResponse, PlayRequest,
+    },
+    session_model::CreateSessionRequest,
+    user_model::CreateUserIdRequest,
+    PROTOCOL_VERSION, PROTOCOL_VERSION_HEADER,
+};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8000";
+const USER_ID_COOKIE: &str = "user_id";
+
+type HttpResponse = Response<Cursor<Vec<u8>>>;
+
+enum Reply {
+    Body(HttpResponse),
+    /// The connection is handed over to the store, which keeps writing to it
+    EventStream {
+        game_id: String,
+        viewer: String,
+        snapshot: ManageGameResponse,
+    },
+}
+
+impl From<HttpResponse> for Reply {
+    fn from(response: HttpResponse) -> Self {
+        Reply::Body(response)
+    }
+}
+
+fn main() {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDR.to_owned());
+    let server = Server::http(&addr).expect("could not bind mock backend");
+    println!("Mock wordle backend listening on http://{addr}");
+
+    let mut store = Store::default();
+    for mut request in server.incoming_requests() {
+        let response = match handle(&mut store, &mut request) {
+            Ok(Reply::Body(response)) => response,
+            Ok(Reply::EventStream {
+                game_id,
+                viewer,
+                snapshot,
+            }) => {
+                println!("{} {} -> event stream", request.method(), request.url());
+                store.open_event_stream(&game_id, &viewer, request.into_writer(), snapshot);
+                continue;
+            }
+            Err(error) => error_response(error),
+        };
+        println!(
+            "{} {} -> {}",
+            request.method(),
+            request.url(),
+            response.status_code().0
+        );
+        if let Err(error) = request.respond(response) {
+            eprintln!("Could not send response: {error}");
+        }
+    }
+}
+
+fn handle(store: &mut Store, request: &mut Request) -> Result<Reply, StoreError> {
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+    let path = path.trim_start_matches("/api/v1");
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let user_id = cookie(request, USER_ID_COOKIE);
+    check_protocol_version(request)?;
+
+    match (request.method().clone(), segments.as_slice()) {
+        (Method::Post, ["user_id"]) => {
+            let req: CreateUserIdRequest = read_json(request)?;
+            let user_id = store.create_user(req.name)?;
+            let set_cookie = format!("{USER_ID_COOKIE}={user_id}; Path=/; HttpOnly; SameSite=Lax");
+            Ok(Response::from_data(vec![])
+                .with_header(Header::from_bytes("Set-Cookie", set_cookie).unwrap())
+                .into())
+        }
+        (Method::Get, ["user_id", "verify"]) => {
+            store.verify_user(user_id.as_deref())?;
+            Ok(Response::from_data(vec![]).into())
+        }
+        (Method::Post, ["create"]) => {
+            let creator = store.verify_user(user_id.as_deref())?;
+            let req: CreateGameRequest = read_json(request)?;
+            let game_id = store.create_game(creator, req)?;
+            Ok(json(&CreateGameResponse { game_id }).into())
+        }
+        (Method::Post, ["session"]) => {
+            let creator = store.verify_user(user_id.as_deref())?;
+            let req: CreateSessionRequest = read_json(request)?;
+            Ok(json(&store.create_session(creator, req)?).into())
+        }
+        (Method::Get, ["session", session_id]) => {
+            let user_id = store.verify_user(user_id.as_deref())?;
+            Ok(json(&store.manage_session(&user_id, session_id)?).into())
+        }
+        (Method::Post, ["game", game_id, "register"]) => {
+            let user_id = store.verify_user(user_id.as_deref())?;
+            let req: JoinGameRequest = read_json_or_default(request)?;
+            store.join_game(user_id, game_id, req)?;
+            Ok(Response::from_data(vec![]).into())
+        }
+        (Method::Post, ["game", game_id, "play"]) => {
+            let user_id = store.verify_user(user_id.as_deref())?;
+            let req: PlayRequest = read_json(request)?;
+            Ok(json(&store.play(&user_id, game_id, req.guess)?).into())
+        }
+        (Method::Get, ["game", game_id, "state"]) => {
+            let user_id = store.verify_user(user_id.as_deref())?;
+            Ok(json(&store.get_state(&user_id, game_id)?).into())
+        }
+        (Method::Get, ["manage", game_id]) => {
+            let user_id = store.verify_user(user_id.as_deref())?;
+            Ok(json(&store.manage(&user_id, game_id)?).into())
+        }
+        (Method::Post, ["manage", game_id, "reveal"]) => {
+            let user_id = store.verify_user(user_id.as_deref())?;
+            Ok(json(&store.reveal(&user_id, game_id)?).into())
+        }
+        (Method::Get, ["manage", game_id, "events"]) => {
+            let user_id = store.verify_user(user_id.as_deref())?;
+            Ok(Reply::EventStream {
+                game_id: game_id.to_string(),
+                snapshot: store.manage(&user_id, game_id)?,
+                viewer: user_id,
+            })
+        }
+        (Method::Get, ["history"]) => {
+            let user_id = store.verify_user(user_id.as_deref())?;
+            Ok(json(&store.history(&user_id)).into())
+        }
+        (Method::Get, ["daily"]) => {
+            store.verify_user(user_id.as_deref())?;
+            Ok(json(&store.daily_game()).into())
+        }
+        (Method::Get, ["history", "played"]) => {
+            let user_id = store.verify_user(user_id.as_deref())?;
+            Ok(json(&store.played(&user_id)).into())
+        }
+        _ => Err(StoreError::NotFound),
+    }
+}
+
+/// Requests without the header are let through so the API can still be poked with curl
+fn check_protocol_version(request: &Request) -> Result<(), StoreError> {
+    let version = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(PROTOCOL_VERSION_HEADER))
+        .map(|header| header.value.as_str());
+    match version {
+        Some(version) if version != PROTOCOL_VERSION.to_string() => Err(StoreError::BadRequest(
+            format!("Unsupported protocol version {version}, expected {PROTOCOL_VERSION}"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn cookie(request: &Request, name: &str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .filter(|header| header.field.equiv("Cookie"))
+        .flat_map(|header| header.value.as_str().split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_owned())
+}
+
+fn read_json<T: DeserializeOwned>(request: &mut Request) -> Result<T, StoreError> {
+    serde_json::from_reader(request.as_reader())
+        .map_err(|error| StoreError::BadRequest(error.to_string()))
+}
+
+/// Like `read_json`, but an empty body stands for `T::default()`
+fn read_json_or_default<T: DeserializeOwned + Default>(
+    request: &mut Request,
+) -> Result<T, StoreError> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|error| StoreError::BadRequest(error.to_string()))?;
+    if body.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_str(&body).map_err(|error| StoreError::BadRequest(error.to_string()))
+}
+
+fn json<T: Serialize>(body: &T) -> HttpResponse {
+    Response::from_data(serde_json::to_vec(body).unwrap())
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap())
+}
+
+fn error_response(error: StoreError) -> HttpResponse {
+    let (status, body) = match error {
+        StoreError::Unauthorized => (401, "Unauthorized".to_owned()),
+        StoreError::NotFound => (404, "Not found".to_owned()),
+        StoreError::Conflict => (409, "Conflict".to_owned()),
+        StoreError::BadRequest(reason) => (400, reason),
+    };
+    Response::from_string(body).with_status_code(status)
+}
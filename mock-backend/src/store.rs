@@ -0,0 +1,926 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use wordle_protocol::{
+    daily::{answer_for_day, day_of},
+    difficulty::random_answer,
+    game_model::{
+        Correctness, CreateGameRequest, DailyGameResponse, GameSummary, GetStateResponse, Guess,
+        HistoryResponse, JoinGameRequest, LeaderboardEvent, ManageGameResponse, PlayResponse,
+        PlayedGame, PlayedHistoryResponse, PlayerResponse, DEFAULT_ATTEMPTS,
+    },
+    language::{to_uppercase, Language},
+    scoring::{hard_mode_violation, score, solved},
+    session_model::{
+        CreateSessionRequest, CreateSessionResponse, ManageSessionResponse, PointsFormula,
+        SessionRound, MAX_ROUNDS, MIN_ROUNDS,
+    },
+};
+
+use crate::events::EventStreams;
+
+#[derive(Debug)]
+pub enum StoreError {
+    Unauthorized,
+    NotFound,
+    Conflict,
+    BadRequest(String),
+}
+
+struct Player {
+    user_id: String,
+    start_time: u64,
+    guesses: Vec<Guess>,
+    hard_mode: bool,
+}
+
+impl Player {
+    fn response(&self, name: &str) -> PlayerResponse {
+        PlayerResponse {
+            name: name.to_owned(),
+            start_time: self.start_time,
+            guesses: self.guesses.clone(),
+            hard_mode: self.hard_mode,
+        }
+    }
+
+    fn has_won(&self) -> bool {
+        solved(self.guesses.iter().map(|guess| guess.guess.as_slice()))
+    }
+
+    fn game_over(&self, max_attempts: usize) -> bool {
+        self.has_won() || self.guesses.len() >= max_attempts
+    }
+}
+
+struct Game {
+    /// Empty for the daily game, which belongs to nobody
+    creator: String,
+    answer: Vec<char>,
+    max_attempts: usize,
+    hard_mode: bool,
+    language: Language,
+    start_time: u64,
+    players: Vec<Player>,
+    daily: bool,
+    /// Picked by the server and kept from the creator until they ask for it
+    random: bool,
+    revealed: bool,
+    /// Session id and 0-based round, for rounds of a session
+    session: Option<(String, usize)>,
+}
+
+impl Game {
+    fn player(&self, user_id: &str) -> Result<&Player, StoreError> {
+        self.players
+            .iter()
+            .find(|player| player.user_id == user_id)
+            .ok_or(StoreError::NotFound)
+    }
+
+    fn answer_hidden(&self) -> bool {
+        self.random && !self.revealed
+    }
+
+    /// Everyone can watch the daily leaderboard, and the creator of a random answer may play
+    /// it, so neither must have the answer given away before they finish: not the answer itself
+    /// nor the letters of any guess
+    fn hides_letters_from(&self, user_id: &str) -> bool {
+        let finished = self
+            .player(user_id)
+            .map(|player| player.game_over(self.max_attempts))
+            .unwrap_or(false);
+        let hidden_from_creator = self.answer_hidden() && user_id == self.creator;
+        (self.daily || hidden_from_creator) && !finished
+    }
+}
+
+/// Keeps only the colors of `guess`, for viewers who must not learn the answer
+pub fn hide_letters(guess: &mut Guess) {
+    for (ch, _) in &mut guess.guess {
+        *ch = ' ';
+    }
+}
+
+struct Session {
+    creator: String,
+    game_ids: Vec<String>,
+    points: PointsFormula,
+    /// Players who opted into hard mode in one round, and so play every later round in it
+    hard_mode_players: HashSet<String>,
+}
+
+/// In-memory stand-in for the wordle backend's database. Everything is lost on restart.
+#[derive(Default)]
+pub struct Store {
+    users: HashMap<String, String>,
+    games: HashMap<String, Game>,
+    /// Game ids of the daily puzzles, by day
+    daily: HashMap<u64, String>,
+    sessions: HashMap<String, Session>,
+    events: EventStreams,
+    next_id: u64,
+}
+
+impl Store {
+    pub fn create_user(&mut self, name: String) -> Result<String, StoreError> {
+        if name.is_empty() {
+            return Err(StoreError::BadRequest("Name must not be empty".to_owned()));
+        }
+        if self.users.values().any(|existing| *existing == name) {
+            return Err(StoreError::Conflict);
+        }
+        let user_id = self.new_id();
+        self.users.insert(user_id.clone(), name);
+        Ok(user_id)
+    }
+
+    pub fn verify_user(&self, user_id: Option<&str>) -> Result<String, StoreError> {
+        user_id
+            .filter(|user_id| self.users.contains_key(*user_id))
+            .map(str::to_owned)
+            .ok_or(StoreError::Unauthorized)
+    }
+
+    pub fn create_game(
+        &mut self,
+        creator: String,
+        req: CreateGameRequest,
+    ) -> Result<String, StoreError> {
+        req.validate().map_err(StoreError::BadRequest)?;
+        let CreateGameRequest {
+            answer,
+            max_attempts,
+            hard_mode,
+            language,
+            random_answer: random,
+            difficulty,
+            ..
+        } = req;
+        let answer: Vec<char> = if random {
+            random_answer(difficulty, now() ^ self.next_id)
+                .chars()
+                .collect()
+        } else {
+            answer.chars().map(to_uppercase).collect()
+        };
+        let game_id = self.new_id();
+        self.games.insert(
+            game_id.clone(),
+            Game {
+                creator,
+                answer,
+                max_attempts,
+                hard_mode,
+                language,
+                start_time: now(),
+                players: vec![],
+                daily: false,
+                random,
+                revealed: false,
+                session: None,
+            },
+        );
+        Ok(game_id)
+    }
+
+    /// Creates one game per round; if any round is invalid none of them are kept
+    pub fn create_session(
+        &mut self,
+        creator: String,
+        req: CreateSessionRequest,
+    ) -> Result<CreateSessionResponse, StoreError> {
+        if !(MIN_ROUNDS..=MAX_ROUNDS).contains(&req.rounds.len()) {
+            return Err(StoreError::BadRequest(format!(
+                "Sessions have between {MIN_ROUNDS} and {MAX_ROUNDS} rounds"
+            )));
+        }
+        let session_id = self.new_id();
+        let mut game_ids = vec![];
+        for (round, game) in req.rounds.into_iter().enumerate() {
+            match self.create_game(creator.clone(), game) {
+                Ok(game_id) => {
+                    self.games.get_mut(&game_id).unwrap().session =
+                        Some((session_id.clone(), round));
+                    game_ids.push(game_id);
+                }
+                Err(error) => {
+                    for game_id in game_ids {
+                        self.games.remove(&game_id);
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        self.sessions.insert(
+            session_id.clone(),
+            Session {
+                creator,
+                game_ids: game_ids.clone(),
+                points: req.points,
+                hard_mode_players: HashSet::new(),
+            },
+        );
+        Ok(CreateSessionResponse {
+            session_id,
+            game_ids,
+        })
+    }
+
+    pub fn manage_session(
+        &self,
+        user_id: &str,
+        session_id: &str,
+    ) -> Result<ManageSessionResponse, StoreError> {
+        let session = self.sessions.get(session_id).ok_or(StoreError::NotFound)?;
+        if session.creator != user_id {
+            return Err(StoreError::Unauthorized);
+        }
+        Ok(ManageSessionResponse {
+            game_ids: session.game_ids.clone(),
+            rounds: session
+                .game_ids
+                .iter()
+                .map(|game_id| self.manage(user_id, game_id))
+                .collect::<Result<_, _>>()?,
+            points: session.points,
+        })
+    }
+
+    pub fn daily_game(&mut self) -> DailyGameResponse {
+        let day = day_of(now());
+        if let Some(game_id) = self.daily.get(&day) {
+            return DailyGameResponse {
+                game_id: game_id.clone(),
+                day,
+            };
+        }
+        let game_id = self.new_id();
+        self.games.insert(
+            game_id.clone(),
+            Game {
+                creator: String::new(),
+                answer: answer_for_day(day).chars().collect(),
+                max_attempts: DEFAULT_ATTEMPTS,
+                hard_mode: false,
+                language: Language::English,
+                start_time: now(),
+                players: vec![],
+                daily: true,
+                random: false,
+                revealed: false,
+                session: None,
+            },
+        );
+        self.daily.insert(day, game_id.clone());
+        DailyGameResponse { game_id, day }
+    }
+
+    pub fn join_game(
+        &mut self,
+        user_id: String,
+        game_id: &str,
+        req: JoinGameRequest,
+    ) -> Result<(), StoreError> {
+        let game = self.games.get_mut(game_id).ok_or(StoreError::NotFound)?;
+        match game.player(&user_id) {
+            Ok(player) if player.game_over(game.max_attempts) => Err(StoreError::Conflict),
+            Ok(_) => Ok(()),
+            Err(_) => {
+                let mut opted_in = req.hard_mode;
+                if let Some((session_id, _)) = &game.session {
+                    let players = &mut self.sessions.get_mut(session_id).unwrap().hard_mode_players;
+                    if opted_in {
+                        players.insert(user_id.clone());
+                    }
+                    opted_in |= players.contains(&user_id);
+                }
+                let player = Player {
+                    user_id,
+                    start_time: now(),
+                    guesses: vec![],
+                    hard_mode: game.hard_mode || opted_in,
+                };
+                let event =
+                    LeaderboardEvent::PlayerJoined(player.response(&self.users[&player.user_id]));
+                game.players.push(player);
+                self.events.publish(game_id, event);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn play(
+        &mut self,
+        user_id: &str,
+        game_id: &str,
+        guess: Vec<char>,
+    ) -> Result<PlayResponse, StoreError> {
+        let game = self.games.get_mut(game_id).ok_or(StoreError::NotFound)?;
+        if guess.len() != game.answer.len() {
+            return Err(StoreError::BadRequest(format!(
+                "Guess must be {} letters long",
+                game.answer.len()
+            )));
+        }
+        let guess: Vec<char> = guess.into_iter().map(to_uppercase).collect();
+        if !guess.iter().all(|&ch| game.language.is_letter(ch)) {
+            return Err(StoreError::BadRequest(format!(
+                "Guess must only use the letters {}",
+                game.language.alphabet()
+            )));
+        }
+        let max_attempts = game.max_attempts;
+        let letters_hidden = game.hides_letters_from(user_id);
+        let scored: Vec<(char, Correctness)> = guess
+            .iter()
+            .copied()
+            .zip(score(&guess, &game.answer))
+            .collect();
+        let player = game
+            .players
+            .iter_mut()
+            .find(|player| player.user_id == user_id)
+            .ok_or(StoreError::NotFound)?;
+        if player.game_over(max_attempts) {
+            return Err(StoreError::Conflict);
+        }
+        if player.hard_mode {
+            let previous: Vec<_> = player
+                .guesses
+                .iter()
+                .map(|guess| guess.guess.clone())
+                .collect();
+            if let Some(violation) = hard_mode_violation(&guess, &previous) {
+                return Err(StoreError::BadRequest(violation.to_string()));
+            }
+        }
+        let guess = Guess {
+            guess: scored.clone(),
+            submit_time: now(),
+        };
+        player.guesses.push(guess.clone());
+        let game_over = player.game_over(max_attempts);
+        self.events.publish(
+            game_id,
+            LeaderboardEvent::Guess {
+                name: self.users[user_id].clone(),
+                guess,
+            },
+        );
+        if letters_hidden && game_over {
+            let snapshot = LeaderboardEvent::Snapshot(self.manage(user_id, game_id)?);
+            self.events.unredact(game_id, user_id, snapshot);
+        }
+        Ok(PlayResponse {
+            game_over,
+            guess: scored,
+        })
+    }
+
+    pub fn get_state(&self, user_id: &str, game_id: &str) -> Result<GetStateResponse, StoreError> {
+        let game = self.games.get(game_id).ok_or(StoreError::NotFound)?;
+        let player = game.player(user_id)?;
+        Ok(GetStateResponse {
+            game_over: player.game_over(game.max_attempts),
+            guesses: player
+                .guesses
+                .iter()
+                .map(|guess| guess.guess.clone())
+                .collect(),
+            word_length: game.answer.len(),
+            max_attempts: game.max_attempts,
+            hard_mode: player.hard_mode,
+            language: game.language,
+            session: game.session.as_ref().map(|(session_id, round)| {
+                let game_ids = &self.sessions[session_id].game_ids;
+                SessionRound {
+                    session_id: session_id.clone(),
+                    round: *round,
+                    rounds: game_ids.len(),
+                    next_game_id: game_ids.get(round + 1).cloned(),
+                }
+            }),
+        })
+    }
+
+    pub fn manage(&self, user_id: &str, game_id: &str) -> Result<ManageGameResponse, StoreError> {
+        let game = self.games.get(game_id).ok_or(StoreError::NotFound)?;
+        if game.creator != user_id && !game.daily {
+            return Err(StoreError::Unauthorized);
+        }
+        let hide_letters_from_viewer = game.hides_letters_from(user_id);
+        let answer = if hide_letters_from_viewer || game.answer_hidden() {
+            String::new()
+        } else {
+            game.answer.iter().collect()
+        };
+        Ok(ManageGameResponse {
+            start_time: game.start_time,
+            players: game
+                .players
+                .iter()
+                .map(|player| {
+                    let mut response = player.response(&self.users[&player.user_id]);
+                    if hide_letters_from_viewer {
+                        response.guesses.iter_mut().for_each(hide_letters);
+                    }
+                    response
+                })
+                .collect(),
+            answer,
+            max_attempts: game.max_attempts,
+            hard_mode: game.hard_mode,
+            revealable: game.answer_hidden(),
+        })
+    }
+
+    /// Shows a random answer to its creator from now on
+    pub fn reveal(
+        &mut self,
+        user_id: &str,
+        game_id: &str,
+    ) -> Result<ManageGameResponse, StoreError> {
+        let game = self.games.get_mut(game_id).ok_or(StoreError::NotFound)?;
+        if game.creator != user_id {
+            return Err(StoreError::Unauthorized);
+        }
+        game.revealed = true;
+        let resp = self.manage(user_id, game_id)?;
+        self.events
+            .unredact(game_id, user_id, LeaderboardEvent::Snapshot(resp.clone()));
+        Ok(resp)
+    }
+
+    pub fn history(&self, user_id: &str) -> HistoryResponse {
+        let mut games: Vec<GameSummary> = self
+            .games
+            .iter()
+            .filter(|(_, game)| game.creator == user_id)
+            .map(|(game_id, game)| GameSummary {
+                game_id: game_id.clone(),
+                answer: if game.answer_hidden() {
+                    String::new()
+                } else {
+                    game.answer.iter().collect()
+                },
+                start_time: game.start_time,
+                language: game.language,
+            })
+            .collect();
+        games.sort_by_key(|game| std::cmp::Reverse(game.start_time));
+        HistoryResponse { games }
+    }
+
+    pub fn played(&self, user_id: &str) -> PlayedHistoryResponse {
+        let mut games: Vec<PlayedGame> = self
+            .games
+            .iter()
+            .filter_map(|(game_id, game)| {
+                let player = game.player(user_id).ok()?;
+                if !player.game_over(game.max_attempts) {
+                    return None;
+                }
+                Some(PlayedGame {
+                    game_id: game_id.clone(),
+                    finish_time: player.guesses.last()?.submit_time,
+                    solved: player.has_won(),
+                    attempts: player.guesses.len(),
+                    max_attempts: game.max_attempts,
+                    word_length: game.answer.len(),
+                })
+            })
+            .collect();
+        games.sort_by_key(|game| game.finish_time);
+        PlayedHistoryResponse { games }
+    }
+
+    /// Starts a `/manage/{id}/events` stream on the raw connection `stream`. `snapshot` comes
+    /// from `manage`, which has already checked that `viewer` may watch the game.
+    pub fn open_event_stream(
+        &mut self,
+        game_id: &str,
+        viewer: &str,
+        stream: Box<dyn Write + Send>,
+        snapshot: ManageGameResponse,
+    ) {
+        let redacted = self
+            .games
+            .get(game_id)
+            .is_some_and(|game| game.hides_letters_from(viewer));
+        self.events.open(
+            game_id,
+            viewer,
+            redacted,
+            stream,
+            LeaderboardEvent::Snapshot(snapshot),
+        );
+    }
+
+    /// 24 hex characters, the same shape as the MongoDB object ids handed out by the real backend
+    fn new_id(&mut self) -> String {
+        self.next_id += 1;
+        format!("{:08x}{:016x}", now() / 1000, self.next_id)
+    }
+}
+
+/// Milliseconds since the unix epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// One store with a creator and a player registered
+    fn store() -> (Store, String, String) {
+        let mut store = Store::default();
+        let creator = store.create_user("host".to_owned()).unwrap();
+        let player = store.create_user("player".to_owned()).unwrap();
+        (store, creator, player)
+    }
+
+    fn request(json: &str) -> CreateGameRequest {
+        serde_json::from_str(json).unwrap()
+    }
+
+    /// A random game whose answer is then pinned to CRATE
+    fn random_game(store: &mut Store, creator: &str) -> String {
+        let game_id = store
+            .create_game(
+                creator.to_owned(),
+                request(r#"{"answer":"","random_answer":true}"#),
+            )
+            .unwrap();
+        store.games.get_mut(&game_id).unwrap().answer = "CRATE".chars().collect();
+        game_id
+    }
+
+    fn join(store: &mut Store, user_id: &str, game_id: &str) {
+        join_with(store, user_id, game_id, false);
+    }
+
+    fn join_with(store: &mut Store, user_id: &str, game_id: &str, hard_mode: bool) {
+        let req = JoinGameRequest { hard_mode };
+        store.join_game(user_id.to_owned(), game_id, req).unwrap();
+    }
+
+    /// A session whose rounds are the given games
+    fn session(store: &mut Store, creator: &str, rounds: &[&str]) -> Vec<String> {
+        let rounds = rounds.iter().map(|json| request(json)).collect();
+        let req = CreateSessionRequest {
+            rounds,
+            points: PointsFormula::default(),
+        };
+        store
+            .create_session(creator.to_owned(), req)
+            .unwrap()
+            .game_ids
+    }
+
+    fn hard_mode(store: &Store, user_id: &str, game_id: &str) -> bool {
+        store.get_state(user_id, game_id).unwrap().hard_mode
+    }
+
+    fn play(store: &mut Store, user_id: &str, game_id: &str, guess: &str) -> PlayResponse {
+        store
+            .play(user_id, game_id, guess.chars().collect())
+            .unwrap()
+    }
+
+    fn letters(resp: &ManageGameResponse) -> Vec<String> {
+        resp.players
+            .iter()
+            .flat_map(|player| &player.guesses)
+            .map(|guess| guess.guess.iter().map(|(ch, _)| ch).collect())
+            .collect()
+    }
+
+    /// Collects what the store writes to an event stream
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Capture {
+        fn events(&self) -> Vec<LeaderboardEvent> {
+            let written = String::from_utf8(self.0.lock().unwrap().clone()).unwrap();
+            written
+                .lines()
+                .filter_map(|line| line.strip_prefix("data: "))
+                .map(|data| serde_json::from_str(data).unwrap())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn random_answer_creator_sees_only_colors_until_reveal() {
+        let (mut store, creator, player) = store();
+        let game_id = random_game(&mut store, &creator);
+        join(&mut store, &player, &game_id);
+        play(&mut store, &player, &game_id, "CRATE");
+
+        let hidden = store.manage(&creator, &game_id).unwrap();
+        assert_eq!(hidden.answer, "");
+        assert!(hidden.revealable);
+        assert_eq!(letters(&hidden), ["     "]);
+
+        let revealed = store.reveal(&creator, &game_id).unwrap();
+        assert_eq!(revealed.answer, "CRATE");
+        assert!(!revealed.revealable);
+        assert_eq!(letters(&revealed), ["CRATE"]);
+    }
+
+    #[test]
+    fn random_answer_creator_sees_letters_once_they_finish() {
+        let (mut store, creator, player) = store();
+        let game_id = random_game(&mut store, &creator);
+        join(&mut store, &player, &game_id);
+        join(&mut store, &creator, &game_id);
+        play(&mut store, &player, &game_id, "CRANE");
+        assert_eq!(
+            letters(&store.manage(&creator, &game_id).unwrap()),
+            ["     "]
+        );
+
+        assert!(play(&mut store, &creator, &game_id, "CRATE").game_over);
+        let resp = store.manage(&creator, &game_id).unwrap();
+        assert_eq!(letters(&resp), ["CRANE", "CRATE"]);
+        // Finishing shows the guesses, but the answer stays back until it is revealed
+        assert_eq!(resp.answer, "");
+    }
+
+    #[test]
+    fn reveal_unredacts_the_creator_event_stream() {
+        let (mut store, creator, player) = store();
+        let game_id = random_game(&mut store, &creator);
+        join(&mut store, &player, &game_id);
+        let capture = Capture::default();
+        let snapshot = store.manage(&creator, &game_id).unwrap();
+        store.open_event_stream(&game_id, &creator, Box::new(capture.clone()), snapshot);
+
+        play(&mut store, &player, &game_id, "CRANE");
+        store.reveal(&creator, &game_id).unwrap();
+        play(&mut store, &player, &game_id, "CRATE");
+
+        let guesses: Vec<String> = capture
+            .events()
+            .into_iter()
+            .filter_map(|event| match event {
+                LeaderboardEvent::Guess { guess, .. } => {
+                    Some(guess.guess.iter().map(|(ch, _)| ch).collect())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(guesses, ["     ", "CRATE"]);
+        // The reveal resends the game so far, letters included
+        assert!(capture.events().iter().any(|event| matches!(
+            event,
+            LeaderboardEvent::Snapshot(game) if letters(game) == ["CRANE"]
+        )));
+    }
+
+    #[test]
+    fn opting_into_hard_mode_carries_over_to_later_rounds() {
+        let (mut store, creator, player) = store();
+        let rounds = session(
+            &mut store,
+            &creator,
+            &[r#"{"answer":"crate"}"#, r#"{"answer":"plumb"}"#],
+        );
+        join_with(&mut store, &player, &rounds[0], true);
+        join(&mut store, &player, &rounds[1]);
+        assert!(hard_mode(&store, &player, &rounds[1]));
+    }
+
+    #[test]
+    fn enforced_hard_mode_stays_in_its_own_round() {
+        let (mut store, creator, player) = store();
+        let rounds = session(
+            &mut store,
+            &creator,
+            &[
+                r#"{"answer":"crate","hard_mode":true}"#,
+                r#"{"answer":"plumb"}"#,
+            ],
+        );
+        join(&mut store, &player, &rounds[0]);
+        assert!(hard_mode(&store, &player, &rounds[0]));
+        join(&mut store, &player, &rounds[1]);
+        assert!(!hard_mode(&store, &player, &rounds[1]));
+    }
+
+    fn game(store: &mut Store, creator: &str, json: &str) -> String {
+        store
+            .create_game(creator.to_owned(), request(json))
+            .unwrap()
+    }
+
+    fn try_play(store: &mut Store, user_id: &str, game_id: &str, guess: &str) -> StoreError {
+        store
+            .play(user_id, game_id, guess.chars().collect())
+            .unwrap_err()
+    }
+
+    #[test]
+    fn play_scores_guesses_until_the_game_is_over() {
+        let (mut store, creator, player) = store();
+        let game_id = game(
+            &mut store,
+            &creator,
+            r#"{"answer":"crate","max_attempts":3}"#,
+        );
+        join(&mut store, &player, &game_id);
+
+        let resp = play(&mut store, &player, &game_id, "trace");
+        assert!(!resp.game_over);
+        assert_eq!(resp.guess[0], ('T', Correctness::IncorrectPosition));
+        assert!(!play(&mut store, &player, &game_id, "plumb").game_over);
+        assert!(play(&mut store, &player, &game_id, "sound").game_over);
+
+        assert!(matches!(
+            try_play(&mut store, &player, &game_id, "crate"),
+            StoreError::Conflict
+        ));
+        let req = JoinGameRequest { hard_mode: false };
+        assert!(matches!(
+            store.join_game(player.clone(), &game_id, req),
+            Err(StoreError::Conflict)
+        ));
+    }
+
+    #[test]
+    fn play_rejects_malformed_guesses_and_strangers() {
+        let (mut store, creator, player) = store();
+        let game_id = game(&mut store, &creator, r#"{"answer":"crate"}"#);
+        assert!(matches!(
+            try_play(&mut store, &player, &game_id, "crate"),
+            StoreError::NotFound
+        ));
+        join(&mut store, &player, &game_id);
+        assert!(matches!(
+            try_play(&mut store, &player, &game_id, "crates"),
+            StoreError::BadRequest(_)
+        ));
+        assert!(matches!(
+            try_play(&mut store, &player, &game_id, "cr4te"),
+            StoreError::BadRequest(_)
+        ));
+        assert!(matches!(
+            try_play(&mut store, &player, "unknown", "crate"),
+            StoreError::NotFound
+        ));
+    }
+
+    #[test]
+    fn play_enforces_hard_mode_for_the_game_or_the_player() {
+        let (mut store, creator, player) = store();
+        let enforced = game(
+            &mut store,
+            &creator,
+            r#"{"answer":"crate","hard_mode":true}"#,
+        );
+        let optional = game(&mut store, &creator, r#"{"answer":"crate"}"#);
+        join(&mut store, &player, &enforced);
+        join_with(&mut store, &player, &optional, true);
+        join(&mut store, &creator, &optional);
+
+        for game_id in [&enforced, &optional] {
+            play(&mut store, &player, game_id, "crane");
+            assert!(matches!(
+                try_play(&mut store, &player, game_id, "plumb"),
+                StoreError::BadRequest(_)
+            ));
+            assert!(play(&mut store, &player, game_id, "crate").game_over);
+        }
+        // Without hard mode the hints can be ignored
+        play(&mut store, &creator, &optional, "crane");
+        play(&mut store, &creator, &optional, "plumb");
+    }
+
+    #[test]
+    fn manage_is_open_to_the_creator_only() {
+        let (mut store, creator, player) = store();
+        let game_id = game(&mut store, &creator, r#"{"answer":"crate"}"#);
+        join(&mut store, &player, &game_id);
+        play(&mut store, &player, &game_id, "crane");
+
+        let resp = store.manage(&creator, &game_id).unwrap();
+        assert_eq!(resp.answer, "CRATE");
+        assert!(!resp.revealable);
+        assert_eq!(letters(&resp), ["CRANE"]);
+        assert!(matches!(
+            store.manage(&player, &game_id),
+            Err(StoreError::Unauthorized)
+        ));
+        assert!(matches!(
+            store.reveal(&player, &game_id),
+            Err(StoreError::Unauthorized)
+        ));
+        assert!(matches!(
+            store.manage(&creator, "unknown"),
+            Err(StoreError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn daily_leaderboard_shows_letters_only_to_finished_players() {
+        let (mut store, early, late) = store();
+        let game_id = store.daily_game().game_id;
+        store.games.get_mut(&game_id).unwrap().answer = "CRATE".chars().collect();
+        join(&mut store, &early, &game_id);
+        join(&mut store, &late, &game_id);
+        play(&mut store, &early, &game_id, "crate");
+
+        let finished = store.manage(&early, &game_id).unwrap();
+        assert_eq!(finished.answer, "CRATE");
+        assert_eq!(letters(&finished), ["CRATE"]);
+        let playing = store.manage(&late, &game_id).unwrap();
+        assert_eq!(playing.answer, "");
+        assert_eq!(letters(&playing), ["     "]);
+        assert_eq!(store.daily_game().game_id, game_id);
+    }
+
+    #[test]
+    fn failed_session_keeps_none_of_its_rounds() {
+        let (mut store, creator, _) = store();
+        let req = CreateSessionRequest {
+            rounds: vec![
+                request(r#"{"answer":"crate"}"#),
+                request(r#"{"answer":"cr"}"#),
+            ],
+            points: PointsFormula::default(),
+        };
+        assert!(matches!(
+            store.create_session(creator.clone(), req),
+            Err(StoreError::BadRequest(_))
+        ));
+        assert!(store.games.is_empty());
+        assert!(store.sessions.is_empty());
+
+        let req = CreateSessionRequest {
+            rounds: vec![],
+            points: PointsFormula::default(),
+        };
+        assert!(matches!(
+            store.create_session(creator, req),
+            Err(StoreError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn session_rounds_point_to_the_next_round() {
+        let (mut store, creator, player) = store();
+        let rounds = session(
+            &mut store,
+            &creator,
+            &[r#"{"answer":"crate"}"#, r#"{"answer":"plumb"}"#],
+        );
+        join(&mut store, &player, &rounds[0]);
+        let first = store
+            .get_state(&player, &rounds[0])
+            .unwrap()
+            .session
+            .unwrap();
+        assert_eq!((first.round, first.rounds), (0, 2));
+        assert_eq!(first.next_game_id.as_ref(), Some(&rounds[1]));
+
+        join(&mut store, &player, &rounds[1]);
+        let last = store
+            .get_state(&player, &rounds[1])
+            .unwrap()
+            .session
+            .unwrap();
+        assert_eq!(last.next_game_id, None);
+
+        let session_id = first.session_id;
+        assert_eq!(
+            store
+                .manage_session(&creator, &session_id)
+                .unwrap()
+                .game_ids,
+            rounds
+        );
+        assert!(matches!(
+            store.manage_session(&player, &session_id),
+            Err(StoreError::Unauthorized)
+        ));
+    }
+}
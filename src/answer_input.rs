@@ -1,33 +1,76 @@
-use reqwasm::http::Request;
-use web_sys::RequestCredentials;
+use std::fmt::Display;
+
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
 #[allow(unused, dead_code)]
 use yew::{classes, html, Component, Context, Html, Properties};
 use yew_router::{history::History, prelude::RouterScopeExt};
 
 use crate::{
+    api::{ApiError, WordleApi},
     charcell::{CharCellState, Correctness, FilledState},
     check_user_set,
-    game_model::{CreateGameRequest, CreateGameResponse},
+    daily::DAY_MS,
+    difficulty::Difficulty,
+    game_model::{
+        CreateGameRequest, CreateGameResponse, GameSummary, HistoryResponse, DEFAULT_ATTEMPTS,
+        DEFAULT_WORD_LENGTH, MAX_ATTEMPTS, MAX_WORD_LENGTH, MIN_ATTEMPTS, MIN_WORD_LENGTH,
+    },
     keyboard::{Keyboard, KeyboardMsg},
+    language::Language,
+    recent_games::{self, GameStatus, RecentGame, Role},
+    session_model::{
+        CreateSessionRequest, CreateSessionResponse, PointsFormula, MAX_ROUNDS, MIN_ROUNDS,
+    },
     snackbar::Snackbar,
     wordle::Word,
-    Route,
+    words, Route,
 };
 
 pub struct AnswerInput {
+    api: WordleApi,
     answer: Vec<CharCellState>,
+    max_attempts: usize,
+    hard_mode: bool,
+    language: Language,
+    answer_source: AnswerSource,
+    /// More than one makes a session, created once every round has been set up
+    rounds: usize,
+    /// Rounds already set up, in order
+    queued_rounds: Vec<CreateGameRequest>,
+    points: PointsFormula,
     cell_i: usize,
     submitted: bool,
     animate: bool,
+    shake: bool,
+    /// Set once the host has been warned that the answer is not in the dictionary
+    allow_unlisted: bool,
+    /// The host's previous games, newest first, to catch reused answers
+    history: Vec<GameSummary>,
+    reuse_policy: ReusePolicy,
+    /// Set once the host has been warned that the answer was used before
+    allow_reused: bool,
     verification_pending: bool,
     toast_msg: Option<String>,
 }
 
 pub enum AnswerInputResponse {
-    CreateGame(Result<CreateGameResponse, reqwasm::Error>),
+    CreateGame(Result<CreateGameResponse, ApiError>),
+    CreateSession(Result<CreateSessionResponse, ApiError>),
+    History(Result<HistoryResponse, ApiError>),
 }
 pub enum AnswerInputMsg {
     KeyboardInput(KeyboardMsg),
+    WordLength(usize),
+    MaxAttempts(usize),
+    Rounds(usize),
+    Points(PointsFormula),
+    ToggleHardMode,
+    Language(Language),
+    AnswerSource(AnswerSource),
+    /// Submits a game whose answer the server picks
+    CreateRandom,
     VerifyUserResponse(bool),
     ApiResponse(AnswerInputResponse),
 }
@@ -38,13 +81,28 @@ impl Component for AnswerInput {
     type Properties = ();
 
     fn create(ctx: &Context<Self>) -> Self {
+        let api = WordleApi::from_context(ctx);
+        let verified = check_user_set(api.clone());
         ctx.link()
-            .send_future(async { AnswerInputMsg::VerifyUserResponse(check_user_set().await) });
+            .send_future(async { AnswerInputMsg::VerifyUserResponse(verified.await) });
         Self {
-            answer: vec![CharCellState::Empty; 5],
+            api,
+            answer: vec![CharCellState::Empty; DEFAULT_WORD_LENGTH],
+            max_attempts: DEFAULT_ATTEMPTS,
+            hard_mode: false,
+            language: Language::default(),
+            answer_source: AnswerSource::Typed,
+            rounds: MIN_ROUNDS,
+            queued_rounds: vec![],
+            points: PointsFormula::default(),
             cell_i: 0,
             submitted: false,
             animate: false,
+            shake: false,
+            allow_unlisted: false,
+            history: vec![],
+            reuse_policy: ReusePolicy::load(),
+            allow_reused: false,
             verification_pending: true,
             toast_msg: Some("Loading".to_owned()),
         }
@@ -59,24 +117,152 @@ impl Component for AnswerInput {
             AnswerInputMsg::VerifyUserResponse(true) => {
                 self.verification_pending = false;
                 self.toast_msg = None;
+                let api = self.api.clone();
+                ctx.link().send_future(async move {
+                    AnswerInputMsg::ApiResponse(AnswerInputResponse::History(api.history().await))
+                });
                 true
             }
             AnswerInputMsg::KeyboardInput(e) => self.keydown_handler(ctx, e),
+            AnswerInputMsg::WordLength(word_length) => {
+                if self.submitted || word_length == self.answer.len() {
+                    return false;
+                }
+                self.answer = vec![CharCellState::Empty; word_length];
+                self.cell_i = 0;
+                self.allow_unlisted = false;
+                self.allow_reused = false;
+                true
+            }
+            AnswerInputMsg::MaxAttempts(max_attempts) => {
+                if self.submitted {
+                    return false;
+                }
+                self.max_attempts = max_attempts;
+                true
+            }
+            AnswerInputMsg::Rounds(rounds) => {
+                // Cannot drop below the rounds already set up
+                if self.submitted || rounds <= self.queued_rounds.len() {
+                    return false;
+                }
+                self.rounds = rounds;
+                true
+            }
+            AnswerInputMsg::Points(points) => {
+                self.points = points;
+                true
+            }
+            AnswerInputMsg::ToggleHardMode => {
+                if self.submitted {
+                    return false;
+                }
+                self.hard_mode = !self.hard_mode;
+                true
+            }
+            AnswerInputMsg::Language(language) => {
+                if self.submitted || language == self.language {
+                    return false;
+                }
+                self.language = language;
+                self.answer = vec![CharCellState::Empty; self.answer.len()];
+                self.cell_i = 0;
+                self.allow_unlisted = false;
+                self.allow_reused = false;
+                true
+            }
+            AnswerInputMsg::AnswerSource(answer_source) => {
+                if self.submitted || answer_source == self.answer_source {
+                    return false;
+                }
+                if let AnswerSource::Random(_) = answer_source {
+                    // The curated list only has five letter English words
+                    self.language = Language::English;
+                    self.answer = vec![CharCellState::Empty; DEFAULT_WORD_LENGTH];
+                    self.cell_i = 0;
+                    self.allow_unlisted = false;
+                    self.allow_reused = false;
+                }
+                self.answer_source = answer_source;
+                true
+            }
+            AnswerInputMsg::CreateRandom => {
+                if self.submitted {
+                    return false;
+                }
+                self.create_game(ctx, String::new());
+                true
+            }
+            AnswerInputMsg::ApiResponse(AnswerInputResponse::History(Ok(resp))) => {
+                self.history = resp.games;
+                false
+            }
+            AnswerInputMsg::ApiResponse(AnswerInputResponse::History(Err(error))) => {
+                log::error!(
+                    "Could not load previous games; reused answers will not be caught: {error}"
+                );
+                false
+            }
             AnswerInputMsg::ApiResponse(AnswerInputResponse::CreateGame(Ok(resp))) => {
                 log::info!("Created game with ID: {:?}", resp);
+                recent_games::record(RecentGame::new(
+                    &resp.game_id,
+                    Role::Created,
+                    GameStatus::InProgress,
+                ));
                 ctx.link().history().unwrap().push(Route::Manage {
                     game_id: resp.game_id,
                 });
                 true
             }
+            AnswerInputMsg::ApiResponse(AnswerInputResponse::CreateSession(Ok(resp))) => {
+                log::info!("Created session with ID: {:?}", resp);
+                recent_games::record(RecentGame::new(
+                    &resp.session_id,
+                    Role::CreatedSession,
+                    GameStatus::InProgress,
+                ));
+                ctx.link().history().unwrap().push(Route::Session {
+                    session_id: resp.session_id,
+                });
+                true
+            }
+            AnswerInputMsg::ApiResponse(
+                AnswerInputResponse::CreateGame(Err(ApiError::Unauthorized))
+                | AnswerInputResponse::CreateSession(Err(ApiError::Unauthorized)),
+            ) => {
+                ctx.link().history().unwrap().push(Route::Register);
+                false
+            }
             AnswerInputMsg::ApiResponse(AnswerInputResponse::CreateGame(Err(error))) => {
-                log::error!("Could not create a new game: {error:?}");
-                self.toast_msg = Some("An error occurred".to_string());
+                log::error!("Could not create a new game: {error}");
+                self.submitted = false;
+                self.toast_msg = Some(error.message().to_owned());
+                true
+            }
+            AnswerInputMsg::ApiResponse(AnswerInputResponse::CreateSession(Err(error))) => {
+                log::error!("Could not create a new session: {error}");
+                self.toast_msg = Some(match error {
+                    // Rounds were validated before being queued, so the server disagrees about
+                    // one of them and there is no telling which: start the session over
+                    ApiError::Server(400, reason) => {
+                        self.queued_rounds.clear();
+                        format!("{reason}; please set up the rounds again")
+                    }
+                    // Otherwise the last round goes back to being edited so it can be resent
+                    error => {
+                        self.queued_rounds.pop();
+                        error.message().to_owned()
+                    }
+                });
+                self.reset_board();
                 true
             }
         }
     }
 
+    // yew 0.19 html! lowers `<Keyboard>`'s props builder into a bare statement
+    #[allow(clippy::unnecessary_operation)]
     fn view(&self, ctx: &Context<Self>) -> Html {
         let onkeyclick = ctx.link().callback(|e: KeyboardMsg| {
             log::info!("Received KeyboardMsg: {e}");
@@ -91,17 +277,96 @@ impl Component for AnswerInput {
             "text-white",
         ];
 
+        let mut hard_mode_classes =
+            vec!["border", "border-solid", "border-white", "rounded", "p-1"];
+        if self.hard_mode {
+            hard_mode_classes.extend(["bg-white", "text-black"]);
+        }
+
+        let mut options_classes = vec!["w-full", "grid", "gap-y-3", "text-white"];
+
         if self.verification_pending {
             answer_classes.push("hidden");
+            options_classes.push("hidden");
         }
+        let random = matches!(self.answer_source, AnswerSource::Random(_));
+        let last_round = self.queued_rounds.len() + 1 == self.rounds;
+        let points = self.points;
 
         html! {
             <div class={classes!("w-full", "h-full", "grid", "place-content-center")}>
                 <div class={classes!("grid", "w-80", "md:w-100", "lg:w-150",  "h-full", "gap-y-5", "justify-items-center", "content-center")}>
-                    <div class={classes!(answer_classes)}>
-                        <Word text={self.answer.clone()} animate={self.animate}></Word>
+                    <div class={classes!(options_classes)}>
+                        { render_picker(ctx, "Rounds", MIN_ROUNDS..=MAX_ROUNDS, self.rounds, AnswerInputMsg::Rounds) }
+                        if self.rounds > 1 {
+                            <div class={classes!("text-center")}>{format!("Setting up round {} of {}", self.queued_rounds.len() + 1, self.rounds)}</div>
+                            { render_picker(ctx, "Points per solve", [5, 10, 20], self.points.solved, move |solved| AnswerInputMsg::Points(PointsFormula { solved, ..points })) }
+                            { render_picker(ctx, "Per spare attempt", [0, 1, 2, 5], self.points.per_spare_attempt, move |per_spare_attempt| AnswerInputMsg::Points(PointsFormula { per_spare_attempt, ..points })) }
+                            { render_picker(ctx, "Hard mode bonus", [0, 2, 5], self.points.hard_mode_bonus, move |hard_mode_bonus| AnswerInputMsg::Points(PointsFormula { hard_mode_bonus, ..points })) }
+                        }
+                        <div class={classes!("w-full", "flex", "justify-between")}>
+                            <div>{"Answer"}</div>
+                            {
+                                [("Type it", AnswerSource::Typed), ("Random word", AnswerSource::Random(None))].into_iter().map(|(label, answer_source)| {
+                                    let mut classes = vec!["border", "border-solid", "border-white", "rounded", "p-1", "text-xs"];
+                                    if random == matches!(answer_source, AnswerSource::Random(_)) {
+                                        classes.extend(["bg-white", "text-black"]);
+                                    }
+                                    html! {
+                                        <button onclick={ctx.link().callback(move |_| AnswerInputMsg::AnswerSource(answer_source))} class={classes}>{label}</button>
+                                    }
+                                }).collect::<Html>()
+                            }
+                        </div>
+                        if random {
+                            <div class={classes!("w-full", "flex", "justify-between")}>
+                                <div>{"Difficulty"}</div>
+                                {
+                                    std::iter::once(None).chain(Difficulty::ALL.into_iter().map(Some)).map(|difficulty| {
+                                        let mut classes = vec!["border", "border-solid", "border-white", "rounded", "p-1", "text-xs"];
+                                        if self.answer_source == AnswerSource::Random(difficulty) {
+                                            classes.extend(["bg-white", "text-black"]);
+                                        }
+                                        html! {
+                                            <button onclick={ctx.link().callback(move |_| AnswerInputMsg::AnswerSource(AnswerSource::Random(difficulty)))} class={classes}>{difficulty.map_or("Any", Difficulty::name)}</button>
+                                        }
+                                    }).collect::<Html>()
+                                }
+                            </div>
+                        } else {
+                            { render_picker(ctx, "Letters", MIN_WORD_LENGTH..=MAX_WORD_LENGTH, self.answer.len(), AnswerInputMsg::WordLength) }
+                        }
+                        { render_picker(ctx, "Attempts", MIN_ATTEMPTS..=MAX_ATTEMPTS, self.max_attempts, AnswerInputMsg::MaxAttempts) }
+                        <div class={classes!("w-full", "flex", "justify-between", random.then_some("hidden"))}>
+                            <div>{"Language"}</div>
+                            {
+                                Language::ALL.into_iter().map(|language| {
+                                    let mut classes = vec!["border", "border-solid", "border-white", "rounded", "p-1", "text-xs"];
+                                    if language == self.language {
+                                        classes.extend(["bg-white", "text-black"]);
+                                    }
+                                    html! {
+                                        <button onclick={ctx.link().callback(move |_| AnswerInputMsg::Language(language))} class={classes}>{language.name()}</button>
+                                    }
+                                }).collect::<Html>()
+                            }
+                        </div>
+                        <div class={classes!("w-full", "flex", "justify-between")}>
+                            <div>{"Hard mode"}</div>
+                            <button onclick={ctx.link().callback(|_| AnswerInputMsg::ToggleHardMode)} class={classes!(hard_mode_classes)}>{if self.hard_mode { "On" } else { "Off" }}</button>
+                        </div>
                     </div>
-                    <Keyboard display={!self.verification_pending} callback={onkeyclick}></Keyboard>
+                    if random {
+                        <div class={classes!("grid", "gap-y-3", "justify-items-center", "text-white")}>
+                            <div class={classes!("text-xs")}>{"The answer stays hidden from you, so you can join and play too"}</div>
+                            <button onclick={ctx.link().callback(|_| AnswerInputMsg::CreateRandom)} class={classes!("border", "border-solid", "border-white", "rounded", "p-1")}>{if last_round { "Create game" } else { "Add round" }}</button>
+                        </div>
+                    } else {
+                        <div class={classes!(answer_classes)}>
+                            <Word text={self.answer.clone()} animate={self.animate} shake={self.shake}></Word>
+                        </div>
+                    }
+                    <Keyboard display={!self.verification_pending && !random} callback={onkeyclick} language={self.language}></Keyboard>
                     <Snackbar message={self.toast_msg.as_ref().cloned().unwrap_or(String::new())} display={self.toast_msg.is_some()}></Snackbar>
                 </div>
             </div>
@@ -109,15 +374,192 @@ impl Component for AnswerInput {
     }
 }
 
+/// A row of numbered buttons for picking one of the game settings
+fn render_picker<T: Copy + PartialEq + Display + 'static>(
+    ctx: &Context<AnswerInput>,
+    label: &'static str,
+    options: impl IntoIterator<Item = T>,
+    selected: T,
+    msg: impl Fn(T) -> AnswerInputMsg + Copy + 'static,
+) -> Html {
+    html! {
+        <div class={classes!("w-full", "flex", "justify-between")}>
+            <div>{label}</div>
+            {
+                options.into_iter().map(|option| {
+                    let mut classes = vec!["w-8", "border", "border-solid", "border-white", "rounded", "grid", "place-content-center"];
+                    if option == selected {
+                        classes.extend(["bg-white", "text-black"]);
+                    }
+                    html! {
+                        <button onclick={ctx.link().callback(move |_| msg(option))} class={classes}>{option}</button>
+                    }
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
+
+/// Where the answer of the game being created comes from
+#[derive(Clone, Copy, PartialEq)]
+pub enum AnswerSource {
+    /// Typed in by the host, who therefore cannot play
+    Typed,
+    /// Picked by the server from the curated list, optionally from one difficulty tier
+    Random(Option<Difficulty>),
+}
+
+/// How `AnswerInput` treats an answer the host has used before
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReusePolicy {
+    /// Warn once, then allow it
+    Warn,
+    /// Refuse answers last used within `days`; older ones are warned about
+    Block { days: u32 },
+}
+
+impl ReusePolicy {
+    pub const ALL: [ReusePolicy; 4] = [
+        ReusePolicy::Warn,
+        ReusePolicy::Block { days: 7 },
+        ReusePolicy::Block { days: 30 },
+        ReusePolicy::Block { days: 90 },
+    ];
+
+    const STORAGE_KEY: &'static str = "answer_reuse_policy";
+
+    pub fn name(self) -> String {
+        match self {
+            ReusePolicy::Warn => "Warn".to_owned(),
+            ReusePolicy::Block { days } => format!("Block {days} days"),
+        }
+    }
+
+    pub fn load() -> Self {
+        LocalStorage::get(Self::STORAGE_KEY).unwrap_or(ReusePolicy::Warn)
+    }
+
+    pub fn save(self) {
+        if let Err(error) = LocalStorage::set(Self::STORAGE_KEY, self) {
+            log::error!("Could not save answer reuse policy: {error}");
+        }
+    }
+}
+
 impl AnswerInput {
+    /// The message to show if `word` was the answer of one of the host's earlier games in the
+    /// same language and should not be submitted yet
+    fn check_reuse(&mut self, word: &[char]) -> Option<String> {
+        let answer: String = word.iter().collect();
+        let last_used = self
+            .history
+            .iter()
+            .find(|game| game.answer == answer && game.language == self.language)?;
+        let date = js_sys::Date::new(&JsValue::from_f64(last_used.start_time as f64));
+        let date = String::from(date.to_date_string());
+        let age = (js_sys::Date::now() as u64).saturating_sub(last_used.start_time);
+        match self.reuse_policy {
+            ReusePolicy::Block { days } if age < u64::from(days) * DAY_MS => Some(format!(
+                "Used on {date}; answers cannot be reused within {days} days"
+            )),
+            _ if !self.allow_reused => {
+                self.allow_reused = true;
+                Some(format!(
+                    "Used on {date}; press Enter again to use it anyway"
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Rejects a typed answer that an earlier round of the session already uses
+    fn check_repeat(&self, game_req: &CreateGameRequest) -> Result<(), String> {
+        let repeated = |round: &CreateGameRequest| {
+            !round.random_answer
+                && round.answer == game_req.answer
+                && round.language == game_req.language
+        };
+        match self.queued_rounds.iter().position(repeated) {
+            Some(round) if !game_req.random_answer => {
+                Err(format!("Round {} already uses this answer", round + 1))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Sends the game settings to the server, or queues them while a session still has rounds
+    /// to set up; `answer` is ignored for random answers
+    fn create_game(&mut self, ctx: &Context<Self>, answer: String) {
+        self.submitted = true;
+        let (random_answer, difficulty) = match self.answer_source {
+            AnswerSource::Typed => (false, None),
+            AnswerSource::Random(difficulty) => (true, difficulty),
+        };
+        let game_req = CreateGameRequest {
+            answer,
+            word_length: self.answer.len(),
+            max_attempts: self.max_attempts,
+            hard_mode: self.hard_mode,
+            language: self.language,
+            random_answer,
+            difficulty,
+        };
+        // Every round is checked here, as the server cannot say which round of a session failed
+        if let Err(reason) = game_req
+            .validate()
+            .and_then(|()| self.check_repeat(&game_req))
+        {
+            self.submitted = false;
+            self.shake = true;
+            self.toast_msg = Some(reason);
+            return;
+        }
+        let api = self.api.clone();
+        if self.rounds == MIN_ROUNDS {
+            ctx.link().send_future(async move {
+                AnswerInputMsg::ApiResponse(AnswerInputResponse::CreateGame(
+                    api.create_game(&game_req).await,
+                ))
+            });
+            return;
+        }
+        self.queued_rounds.push(game_req);
+        if self.queued_rounds.len() < self.rounds {
+            self.reset_board();
+            self.toast_msg = Some(format!("Round {} saved", self.queued_rounds.len()));
+            return;
+        }
+        let session_req = CreateSessionRequest {
+            rounds: self.queued_rounds.clone(),
+            points: self.points,
+        };
+        ctx.link().send_future(async move {
+            AnswerInputMsg::ApiResponse(AnswerInputResponse::CreateSession(
+                api.create_session(&session_req).await,
+            ))
+        });
+    }
+
+    /// Clears the answer so the next round can be set up
+    fn reset_board(&mut self) {
+        self.submitted = false;
+        self.answer = vec![CharCellState::Empty; self.answer.len()];
+        self.cell_i = 0;
+        self.allow_unlisted = false;
+        self.allow_reused = false;
+    }
+
     fn keydown_handler(&mut self, ctx: &Context<Self>, e: KeyboardMsg) -> bool {
         if self.submitted {
             return false;
         }
         self.animate = false;
+        self.shake = false;
         match e {
             KeyboardMsg::Backspace => {
                 if self.cell_i > 0 {
+                    self.allow_unlisted = false;
+                    self.allow_reused = false;
                     self.cell_i -= 1;
                     self.answer[self.cell_i] = CharCellState::Empty;
                 } else {
@@ -125,8 +567,27 @@ impl AnswerInput {
                 }
             }
             KeyboardMsg::Enter => {
-                if self.cell_i == 5 {
-                    self.submitted = true;
+                if self.cell_i == self.answer.len() {
+                    let word: Vec<char> = self
+                        .answer
+                        .iter()
+                        .filter_map(|css| match css {
+                            CharCellState::Filled(FilledState { ch, .. }) => Some(*ch),
+                            CharCellState::Empty => None,
+                        })
+                        .collect();
+                    if !self.allow_unlisted && !words::is_valid_word(self.language, &word) {
+                        self.allow_unlisted = true;
+                        self.shake = true;
+                        self.toast_msg =
+                            Some("Not in word list; press Enter again to use it anyway".to_owned());
+                        return true;
+                    }
+                    if let Some(toast_msg) = self.check_reuse(&word) {
+                        self.shake = true;
+                        self.toast_msg = Some(toast_msg);
+                        return true;
+                    }
                     let answer: String = self
                         .answer
                         .iter_mut()
@@ -140,41 +601,24 @@ impl AnswerInput {
                         })
                         .collect();
                     log::info!("Set answer to {:?}!", answer);
-                    ctx.link().send_future(async {
-                        let game_req = CreateGameRequest { answer: answer };
-                        AnswerInputMsg::ApiResponse(AnswerInputResponse::CreateGame(
-                            match Request::post("/api/v1/create")
-                                .header("Content-Type", "application/json")
-                                .body(serde_json::to_string(&game_req).unwrap())
-                                .credentials(RequestCredentials::Include)
-                                .send()
-                                .await
-                            {
-                                Ok(resp) => resp.json::<CreateGameResponse>().await,
-                                Err(error) => Err(error),
-                            },
-                        ))
-                    });
+                    self.create_game(ctx, answer);
                 }
                 self.animate = true;
             }
 
-            ch => {
-                let ch: &str = ch.into();
-                if ch.len() == 1 && self.cell_i <= 4 {
+            KeyboardMsg::Letter(ch) => {
+                if self.cell_i < self.answer.len() {
                     self.answer[self.cell_i] = CharCellState::Filled(FilledState {
-                        ch: ch.chars().next().unwrap().to_ascii_uppercase(),
+                        ch,
                         correctness: Correctness::Guess,
                     });
-                    if self.cell_i <= 4 {
-                        self.cell_i += 1;
-                    }
+                    self.cell_i += 1;
                 } else {
                     return false;
                 }
             }
         }
 
-        return true;
+        true
     }
 }
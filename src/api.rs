@@ -0,0 +1,467 @@
+use std::{fmt::Display, future::Future, pin::Pin, rc::Rc};
+
+use gloo::{events::EventListener, timers::callback::Interval};
+use reqwasm::http::Request;
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::JsCast;
+use web_sys::{EventSource, EventSourceInit, MessageEvent, RequestCredentials};
+use wordle_protocol::{PROTOCOL_VERSION, PROTOCOL_VERSION_HEADER};
+use yew::{Callback, Component, Context};
+
+use crate::{
+    game_model::{
+        CreateGameRequest, CreateGameResponse, DailyGameResponse, GetStateResponse,
+        HistoryResponse, JoinGameRequest, LeaderboardEvent, ManageGameResponse, PlayRequest,
+        PlayResponse, PlayedHistoryResponse,
+    },
+    session_model::{CreateSessionRequest, CreateSessionResponse, ManageSessionResponse},
+    user_model::CreateUserIdRequest,
+};
+
+const API_BASE_URL: &str = "/api/v1";
+
+#[derive(Debug)]
+pub enum ApiError {
+    /// The request never produced a response
+    Network(reqwasm::Error),
+    /// 401: the user id cookie is missing or not recognised
+    Unauthorized,
+    /// 404: the game (or user) does not exist
+    NotFound,
+    /// 409: the request clashes with existing state, e.g. a taken name or a finished game
+    Conflict,
+    /// Any other non-2xx status, along with the response body
+    Server(u16, String),
+    /// The response body did not match the expected model
+    Decode(serde_json::Error),
+}
+
+impl ApiError {
+    /// Generic snackbar text; components override the variants they have better wording for.
+    pub fn message(&self) -> &'static str {
+        match self {
+            ApiError::Network(_) => "Could not reach the server",
+            ApiError::Unauthorized => "Please register to continue",
+            ApiError::NotFound => "Not found",
+            ApiError::Conflict => "That is not allowed right now",
+            ApiError::Server(..) => "The server ran into a problem",
+            ApiError::Decode(_) => "Received an unexpected response",
+        }
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::Network(error) => write!(f, "network error: {error}"),
+            ApiError::Unauthorized => write!(f, "unauthorized"),
+            ApiError::NotFound => write!(f, "not found"),
+            ApiError::Conflict => write!(f, "conflict"),
+            ApiError::Server(status, body) => write!(f, "server error {status}: {body}"),
+            ApiError::Decode(error) => write!(f, "could not decode response: {error}"),
+        }
+    }
+}
+
+/// A boxed future that need not be `Send`; everything in the browser runs on one thread
+pub type LocalBoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+/// What a `Transport` got back, before `WordleApi` looks at the status
+pub struct RawResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Carries `WordleApi` requests to a backend. `HttpTransport` talks to the real server; tests
+/// can provide one that answers from memory instead.
+pub trait Transport {
+    /// Sends `body`, which is JSON if present, to `path` relative to the API root
+    fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+    ) -> LocalBoxFuture<'static, Result<RawResponse, ApiError>>;
+
+    /// Opens a server-sent event stream at `path`. `callback` receives each message's data, then
+    /// `None` once the stream fails. Transports without server push keep the default, and
+    /// callers poll instead.
+    fn open_events(&self, _path: &str, _callback: Callback<Option<String>>) -> Option<EventStream> {
+        None
+    }
+}
+
+/// The real backend, reached over HTTP with the user id cookie
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpTransport {
+    base_url: String,
+}
+
+impl Default for HttpTransport {
+    fn default() -> Self {
+        Self {
+            base_url: API_BASE_URL.to_owned(),
+        }
+    }
+}
+
+impl HttpTransport {
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+}
+
+impl Transport for HttpTransport {
+    fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+    ) -> LocalBoxFuture<'static, Result<RawResponse, ApiError>> {
+        let url = self.url(path);
+        Box::pin(async move {
+            let req = match method {
+                Method::Get => Request::get(&url),
+                Method::Post => Request::post(&url).header("Content-Type", "application/json"),
+            };
+            let req = match body {
+                Some(body) => req.body(body),
+                None => req,
+            };
+            let resp = req
+                .header(PROTOCOL_VERSION_HEADER, &PROTOCOL_VERSION.to_string())
+                .credentials(RequestCredentials::Include)
+                .send()
+                .await
+                .map_err(ApiError::Network)?;
+            Ok(RawResponse {
+                status: resp.status(),
+                body: resp.text().await.map_err(ApiError::Network)?,
+            })
+        })
+    }
+
+    /// `EventSource` cannot set headers, so this request goes out without the protocol version.
+    fn open_events(&self, path: &str, callback: Callback<Option<String>>) -> Option<EventStream> {
+        let init = EventSourceInit::new();
+        init.set_with_credentials(true);
+        let source = EventSource::new_with_event_source_init_dict(&self.url(path), &init)
+            .map_err(|error| log::error!("Could not open event stream: {error:?}"))
+            .ok()?;
+        let on_message = callback.clone();
+        let message_listener = EventListener::new(&source, "message", move |e| {
+            let data = e
+                .dyn_ref::<MessageEvent>()
+                .and_then(|e| e.data().as_string())
+                .unwrap_or_default();
+            on_message.emit(Some(data));
+        });
+        // The browser would otherwise keep reconnecting on its own
+        let closing_source = source.clone();
+        let error_listener = EventListener::once(&source, "error", move |_| {
+            closing_source.close();
+            callback.emit(None);
+        });
+        Some(EventStream {
+            source,
+            _message_listener: message_listener,
+            _error_listener: error_listener,
+        })
+    }
+}
+
+/// Client for the wordle backend. `App` provides one to every component through the context;
+/// see `WordleApi::from_context`.
+#[derive(Clone)]
+pub struct WordleApi {
+    transport: Rc<dyn Transport>,
+}
+
+impl Default for WordleApi {
+    fn default() -> Self {
+        Self::new(HttpTransport::default())
+    }
+}
+
+impl PartialEq for WordleApi {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.transport, &other.transport)
+    }
+}
+
+impl WordleApi {
+    pub fn new(transport: impl Transport + 'static) -> Self {
+        Self {
+            transport: Rc::new(transport),
+        }
+    }
+
+    /// The client provided by `App`, or one for the real backend when there is none
+    pub fn from_context<C: Component>(ctx: &Context<C>) -> Self {
+        ctx.link()
+            .context::<Self>(Callback::noop())
+            .map(|(api, _)| api)
+            .unwrap_or_default()
+    }
+
+    pub async fn verify_user(&self) -> Result<bool, ApiError> {
+        match self.send(Method::Get, "/user_id/verify", None).await {
+            Ok(_) => Ok(true),
+            Err(ApiError::Unauthorized) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub async fn register_user(&self, req: &CreateUserIdRequest) -> Result<(), ApiError> {
+        self.send_json("/user_id", req).await?;
+        Ok(())
+    }
+
+    pub async fn create_game(
+        &self,
+        req: &CreateGameRequest,
+    ) -> Result<CreateGameResponse, ApiError> {
+        let resp = self.send_json("/create", req).await?;
+        decode(&resp)
+    }
+
+    pub async fn create_session(
+        &self,
+        req: &CreateSessionRequest,
+    ) -> Result<CreateSessionResponse, ApiError> {
+        let resp = self.send_json("/session", req).await?;
+        decode(&resp)
+    }
+
+    pub async fn session(&self, session_id: &str) -> Result<ManageSessionResponse, ApiError> {
+        let path = format!("/session/{session_id}");
+        let resp = self.send(Method::Get, &path, None).await?;
+        decode(&resp)
+    }
+
+    pub async fn join_game(&self, game_id: &str, req: &JoinGameRequest) -> Result<(), ApiError> {
+        let path = format!("/game/{game_id}/register");
+        self.send_json(&path, req).await?;
+        Ok(())
+    }
+
+    pub async fn play(&self, game_id: &str, req: &PlayRequest) -> Result<PlayResponse, ApiError> {
+        let path = format!("/game/{game_id}/play");
+        let resp = self.send_json(&path, req).await?;
+        decode(&resp)
+    }
+
+    pub async fn get_state(&self, game_id: &str) -> Result<GetStateResponse, ApiError> {
+        let path = format!("/game/{game_id}/state");
+        let resp = self.send(Method::Get, &path, None).await?;
+        decode(&resp)
+    }
+
+    pub async fn manage(&self, game_id: &str) -> Result<ManageGameResponse, ApiError> {
+        let path = format!("/manage/{game_id}");
+        let resp = self.send(Method::Get, &path, None).await?;
+        decode(&resp)
+    }
+
+    /// Stops hiding a random answer from its creator
+    pub async fn reveal(&self, game_id: &str) -> Result<ManageGameResponse, ApiError> {
+        let path = format!("/manage/{game_id}/reveal");
+        let resp = self.send(Method::Post, &path, None).await?;
+        decode(&resp)
+    }
+
+    pub async fn history(&self) -> Result<HistoryResponse, ApiError> {
+        let resp = self.send(Method::Get, "/history", None).await?;
+        decode(&resp)
+    }
+
+    pub async fn daily(&self) -> Result<DailyGameResponse, ApiError> {
+        let resp = self.send(Method::Get, "/daily", None).await?;
+        decode(&resp)
+    }
+
+    pub async fn played(&self) -> Result<PlayedHistoryResponse, ApiError> {
+        let resp = self.send(Method::Get, "/history/played", None).await?;
+        decode(&resp)
+    }
+
+    /// Subscribes to leaderboard updates for `game_id`. `callback` receives `None` once the
+    /// stream fails; it is not reopened, so callers should fall back to polling `manage`.
+    pub fn manage_events(
+        &self,
+        game_id: &str,
+        callback: Callback<Option<LeaderboardEvent>>,
+    ) -> Option<EventStream> {
+        let on_data = Callback::from(move |data: Option<String>| {
+            let Some(data) = data else {
+                return callback.emit(None);
+            };
+            match serde_json::from_str(&data) {
+                Ok(event) => callback.emit(Some(event)),
+                Err(error) => log::error!("Could not decode leaderboard event: {error}"),
+            }
+        });
+        self.transport
+            .open_events(&format!("/manage/{game_id}/events"), on_data)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+    ) -> Result<RawResponse, ApiError> {
+        check_status(self.transport.send(method, path, body).await?)
+    }
+
+    async fn send_json<T: Serialize>(&self, path: &str, body: &T) -> Result<RawResponse, ApiError> {
+        let body = serde_json::to_string(body).unwrap();
+        self.send(Method::Post, path, Some(body)).await
+    }
+}
+
+/// Sends `request(api)` to the component now and every 5 seconds, until the returned `Interval`
+/// is dropped
+pub fn poll<C, F, Fut>(ctx: &Context<C>, request: F) -> Interval
+where
+    C: Component,
+    F: Fn(WordleApi) -> Fut + 'static,
+    Fut: Future<Output = C::Message> + 'static,
+{
+    let link = ctx.link().clone();
+    let api = WordleApi::from_context(ctx);
+    let poll = move || link.send_future(request(api.clone()));
+    poll();
+    Interval::new(5_000, poll)
+}
+
+/// An open `/manage/{id}/events` stream; dropping it closes the connection
+pub struct EventStream {
+    source: EventSource,
+    _message_listener: EventListener,
+    _error_listener: EventListener,
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.source.close();
+    }
+}
+
+fn check_status(resp: RawResponse) -> Result<RawResponse, ApiError> {
+    match resp.status {
+        200..=299 => Ok(resp),
+        401 => Err(ApiError::Unauthorized),
+        404 => Err(ApiError::NotFound),
+        409 => Err(ApiError::Conflict),
+        status => Err(ApiError::Server(status, resp.body)),
+    }
+}
+
+fn decode<T: DeserializeOwned>(resp: &RawResponse) -> Result<T, ApiError> {
+    serde_json::from_str(&resp.body).map_err(ApiError::Decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        future::{ready, Future},
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use super::*;
+
+    /// Answers every request with the same response and remembers what was asked
+    struct FakeTransport {
+        status: u16,
+        body: &'static str,
+        requests: Requests,
+    }
+
+    impl Transport for FakeTransport {
+        fn send(
+            &self,
+            method: Method,
+            path: &str,
+            body: Option<String>,
+        ) -> LocalBoxFuture<'static, Result<RawResponse, ApiError>> {
+            self.requests
+                .borrow_mut()
+                .push((method, path.to_owned(), body));
+            Box::pin(ready(Ok(RawResponse {
+                status: self.status,
+                body: self.body.to_owned(),
+            })))
+        }
+    }
+
+    type Requests = Rc<RefCell<Vec<(Method, String, Option<String>)>>>;
+
+    fn fake(status: u16, body: &'static str) -> (WordleApi, Requests) {
+        let requests = Requests::default();
+        let transport = FakeTransport {
+            status,
+            body,
+            requests: requests.clone(),
+        };
+        (WordleApi::new(transport), requests)
+    }
+
+    /// The fake never waits, so every future completes on its first poll
+    fn run<T>(future: impl Future<Output = T>) -> T {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("the fake transport should never wait"),
+        }
+    }
+
+    #[test]
+    fn verify_user_treats_unauthorized_as_unregistered() {
+        let (api, requests) = fake(401, "");
+        assert!(!run(api.verify_user()).unwrap());
+        assert_eq!(
+            *requests.borrow(),
+            vec![(Method::Get, "/user_id/verify".to_owned(), None)]
+        );
+
+        let (api, _) = fake(204, "");
+        assert!(run(api.verify_user()).unwrap());
+    }
+
+    #[test]
+    fn create_game_posts_json_and_decodes_the_response() {
+        let (api, requests) = fake(200, r#"{"game_id":"abc"}"#);
+        let req: CreateGameRequest = serde_json::from_str(r#"{"answer":"crate"}"#).unwrap();
+        let resp = run(api.create_game(&req)).unwrap();
+        assert_eq!(resp.game_id, "abc");
+
+        let requests = requests.borrow();
+        let (method, path, body) = &requests[0];
+        assert_eq!((*method, path.as_str()), (Method::Post, "/create"));
+        let sent: CreateGameRequest = serde_json::from_str(body.as_deref().unwrap()).unwrap();
+        assert_eq!(sent.answer, req.answer);
+    }
+
+    #[test]
+    fn error_statuses_map_to_api_errors() {
+        let (api, _) = fake(404, "");
+        assert!(matches!(run(api.manage("abc")), Err(ApiError::NotFound)));
+        let (api, _) = fake(409, "");
+        assert!(matches!(run(api.manage("abc")), Err(ApiError::Conflict)));
+        let (api, _) = fake(500, "boom");
+        assert!(matches!(
+            run(api.manage("abc")),
+            Err(ApiError::Server(500, body)) if body == "boom"
+        ));
+        let (api, _) = fake(200, "not json");
+        assert!(matches!(run(api.manage("abc")), Err(ApiError::Decode(_))));
+    }
+}
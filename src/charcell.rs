@@ -27,15 +27,36 @@ pub struct FilledState {
     pub correctness: Correctness,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum CharCellState {
+    #[default]
     Empty,
     Filled(FilledState),
 }
 
-impl Default for CharCellState {
-    fn default() -> Self {
-        Self::Empty
+/// Cells shrink for longer words so that a row still fits the page width
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CharCellSize {
+    Large,
+    Medium,
+    Small,
+}
+
+impl CharCellSize {
+    pub fn for_word_length(word_length: usize) -> Self {
+        match word_length {
+            0..=6 => Self::Large,
+            7 => Self::Medium,
+            _ => Self::Small,
+        }
+    }
+
+    fn classes(self) -> [&'static str; 2] {
+        match self {
+            Self::Large => ["h-12", "w-12"],
+            Self::Medium => ["h-10", "w-10"],
+            Self::Small => ["h-9", "w-9"],
+        }
     }
 }
 
@@ -45,6 +66,8 @@ pub struct CharCellProps {
     pub state: CharCellState,
     #[prop_or((false, 0))]
     pub animate: (bool, u16),
+    #[prop_or(CharCellSize::Large)]
+    pub size: CharCellSize,
 }
 pub struct CharCell;
 
@@ -58,17 +81,20 @@ impl Component for CharCell {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let Self::Properties { state, animate } = ctx.props();
+        let Self::Properties {
+            state,
+            animate,
+            size,
+        } = ctx.props();
 
-        let mut classes = vec![
-            "h-12",
-            "w-12",
+        let mut classes = size.classes().to_vec();
+        classes.extend([
             "border",
             "border-solid",
             "grid",
             "rounded",
             "place-content-center",
-        ];
+        ]);
 
         match state {
             CharCellState::Empty => {
@@ -0,0 +1,229 @@
+use gloo::{file::Blob, timers::callback::Timeout};
+use serde::Serialize;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlAnchorElement, Url};
+use wordle_protocol::ranking::{self, Standing};
+
+use crate::game_model::{Correctness, Guess, ManageGameResponse};
+
+/// File formats the leaderboard can be downloaded in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    /// One row per guess, for pasting into a spreadsheet
+    Csv,
+    /// The whole game, for archiving
+    Json,
+}
+
+#[derive(Serialize)]
+struct ExportedGame<'a> {
+    game_id: &'a str,
+    answer: &'a str,
+    start_time: u64,
+    max_attempts: usize,
+    hard_mode: bool,
+    players: Vec<ExportedPlayer<'a>>,
+}
+
+#[derive(Serialize)]
+struct ExportedPlayer<'a> {
+    rank: usize,
+    name: &'a str,
+    hard_mode: bool,
+    solved: bool,
+    attempts: usize,
+    start_time: u64,
+    elapsed: Option<u64>,
+    guesses: &'a [Guess],
+}
+
+impl ExportFormat {
+    /// Saves the game's results as `wordle-{game_id}.csv` or `.json` in the browser's downloads
+    pub fn download(self, game_id: &str, game: &ManageGameResponse) {
+        let (contents, mime_type, extension) = match self {
+            ExportFormat::Csv => (to_csv(game), "text/csv", "csv"),
+            ExportFormat::Json => (to_json(game_id, game), "application/json", "json"),
+        };
+        if let Err(error) = save(
+            &format!("wordle-{game_id}.{extension}"),
+            &contents,
+            mime_type,
+        ) {
+            log::error!("Could not download results: {error:?}");
+        }
+    }
+}
+
+fn to_json(game_id: &str, game: &ManageGameResponse) -> String {
+    let exported = ExportedGame {
+        game_id,
+        answer: &game.answer,
+        start_time: game.start_time,
+        max_attempts: game.max_attempts,
+        hard_mode: game.hard_mode,
+        players: ranking::rank(&game.players)
+            .into_iter()
+            .map(|standing| ExportedPlayer {
+                rank: standing.rank,
+                name: &standing.player.name,
+                hard_mode: standing.player.hard_mode,
+                solved: standing.solved,
+                attempts: standing.attempts,
+                start_time: standing.player.start_time,
+                elapsed: standing.elapsed,
+                guesses: &standing.player.guesses,
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&exported).unwrap()
+}
+
+const CSV_HEADER: &str =
+    "rank,name,hard_mode,solved,attempts,elapsed_ms,guess_number,guess,correctness,submit_time";
+
+/// Players who have not guessed yet still get a row, with the guess columns left empty
+fn to_csv(game: &ManageGameResponse) -> String {
+    let mut csv = format!("{CSV_HEADER}\r\n");
+    for standing in ranking::rank(&game.players) {
+        let player_columns = player_columns(&standing);
+        if standing.player.guesses.is_empty() {
+            csv.push_str(&format!("{player_columns},,,,\r\n"));
+        }
+        for (i, guess) in standing.player.guesses.iter().enumerate() {
+            let letters: String = guess.guess.iter().map(|&(ch, _)| ch).collect();
+            let correctness: Vec<&str> = guess
+                .guess
+                .iter()
+                .map(|(_, correctness)| match correctness {
+                    Correctness::Correct => "Correct",
+                    Correctness::IncorrectPosition => "IncorrectPosition",
+                    Correctness::Incorrect => "Incorrect",
+                })
+                .collect();
+            csv.push_str(&format!(
+                "{player_columns},{},{},{},{}\r\n",
+                i + 1,
+                csv_field(&letters),
+                correctness.join(" "),
+                guess.submit_time
+            ));
+        }
+    }
+    csv
+}
+
+fn player_columns(standing: &Standing) -> String {
+    format!(
+        "{},{},{},{},{},{}",
+        standing.rank,
+        csv_field(&standing.player.name),
+        standing.player.hard_mode,
+        standing.solved,
+        standing.attempts,
+        standing
+            .elapsed
+            .map(|elapsed| elapsed.to_string())
+            .unwrap_or_default()
+    )
+}
+
+/// Quotes a field that would otherwise break the row, doubling any quotes inside it
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// How long the Blob URL outlives the click; the download may start after `click` returns
+const REVOKE_DELAY_MS: u32 = 10_000;
+
+/// Clicks a temporary link to a Blob URL, which is how a page hands the browser a file it made.
+/// Firefox ignores clicks on links that are not in the document, so the link is attached for
+/// the click.
+fn save(file_name: &str, contents: &str, mime_type: &str) -> Result<(), wasm_bindgen::JsValue> {
+    let blob = Blob::new_with_options(contents, Some(mime_type));
+    let url = Url::create_object_url_with_blob(blob.as_ref())?;
+    let link: HtmlAnchorElement = gloo::utils::document()
+        .create_element("a")?
+        .dyn_into()
+        .map_err(wasm_bindgen::JsValue::from)?;
+    link.set_href(&url);
+    link.set_download(file_name);
+    let body = gloo::utils::body();
+    body.append_child(&link)?;
+    link.click();
+    body.remove_child(&link)?;
+    Timeout::new(REVOKE_DELAY_MS, move || {
+        if let Err(error) = Url::revoke_object_url(&url) {
+            log::error!("Could not revoke the export URL: {error:?}");
+        }
+    })
+    .forget();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game() -> ManageGameResponse {
+        serde_json::from_str(
+            r#"{
+                "start_time": 1000,
+                "answer": "CRATE",
+                "max_attempts": 6,
+                "hard_mode": false,
+                "revealable": false,
+                "players": [
+                    { "name": "Waiting, \"Bob\"", "start_time": 1000, "hard_mode": false, "guesses": [] },
+                    { "name": "alice", "start_time": 1000, "hard_mode": true, "guesses": [
+                        { "submit_time": 2000, "guess": [["C","Correct"],["R","Correct"],["A","Correct"],["N","Incorrect"],["E","Correct"]] },
+                        { "submit_time": 3000, "guess": [["C","Correct"],["R","Correct"],["A","Correct"],["T","Correct"],["E","Correct"]] }
+                    ] }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn csv_has_a_row_per_guess_and_one_for_players_without_guesses() {
+        let csv = to_csv(&game());
+        let rows: Vec<&str> = csv.split_terminator("\r\n").collect();
+        assert_eq!(
+            rows,
+            [
+                CSV_HEADER,
+                "1,alice,true,true,2,2000,1,CRANE,Correct Correct Correct Incorrect Correct,2000",
+                "1,alice,true,true,2,2000,2,CRATE,Correct Correct Correct Correct Correct,3000",
+                "2,\"Waiting, \"\"Bob\"\"\",false,false,0,,,,,",
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("alice"), "alice");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn json_lists_ranked_players_with_their_guesses() {
+        let json: serde_json::Value = serde_json::from_str(&to_json("abc", &game())).unwrap();
+        assert_eq!(json["game_id"], "abc");
+        assert_eq!(json["answer"], "CRATE");
+        let players = json["players"].as_array().unwrap();
+        assert_eq!(players[0]["name"], "alice");
+        assert_eq!(players[0]["rank"], 1);
+        assert_eq!(players[0]["solved"], true);
+        assert_eq!(players[0]["elapsed"], 2000);
+        assert_eq!(players[0]["guesses"].as_array().unwrap().len(), 2);
+        assert_eq!(players[1]["rank"], 2);
+        assert_eq!(players[1]["elapsed"], serde_json::Value::Null);
+        assert!(players[1]["guesses"].as_array().unwrap().is_empty());
+    }
+}
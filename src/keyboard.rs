@@ -1,82 +1,117 @@
+use std::collections::HashMap;
 use std::fmt::Display;
 
-use crate::charcell::Correctness;
+use crate::{
+    charcell::Correctness,
+    language::{to_uppercase, Language},
+};
+use gloo::{
+    events::EventListener,
+    storage::{LocalStorage, Storage},
+};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, KeyboardEvent};
 #[allow(unused, dead_code)]
 use yew::{classes, html, Callback, Component, Context, Html, Properties};
 
-pub struct Keyboard;
+pub struct Keyboard {
+    /// Feeds physical key presses into the same callback as the on-screen keys; dropping it
+    /// removes the listener
+    _keydown_listener: EventListener,
+}
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum KeyboardMsg {
-    A = 0,
-    B,
-    C,
-    D,
-    E,
-    F,
-    G,
-    H,
-    I,
-    J,
-    K,
-    L,
-    M,
-    N,
-    O,
-    P,
-    Q,
-    R,
-    S,
-    T,
-    U,
-    V,
-    W,
-    X,
-    Y,
-    Z,
-    Backspace = 26,
-    Enter = 27,
+    /// Always uppercase, see `language::to_uppercase`
+    Letter(char),
+    Backspace,
+    Enter,
 }
 
-impl From<KeyboardMsg> for &'static str {
-    fn from(km: KeyboardMsg) -> &'static str {
-        match km {
-            KeyboardMsg::A => "A",
-            KeyboardMsg::B => "B",
-            KeyboardMsg::C => "C",
-            KeyboardMsg::D => "D",
-            KeyboardMsg::E => "E",
-            KeyboardMsg::F => "F",
-            KeyboardMsg::G => "G",
-            KeyboardMsg::H => "H",
-            KeyboardMsg::I => "I",
-            KeyboardMsg::J => "J",
-            KeyboardMsg::K => "K",
-            KeyboardMsg::L => "L",
-            KeyboardMsg::M => "M",
-            KeyboardMsg::N => "N",
-            KeyboardMsg::O => "O",
-            KeyboardMsg::P => "P",
-            KeyboardMsg::Q => "Q",
-            KeyboardMsg::R => "R",
-            KeyboardMsg::S => "S",
-            KeyboardMsg::T => "T",
-            KeyboardMsg::U => "U",
-            KeyboardMsg::V => "V",
-            KeyboardMsg::W => "W",
-            KeyboardMsg::X => "X",
-            KeyboardMsg::Y => "Y",
-            KeyboardMsg::Z => "Z",
-            KeyboardMsg::Backspace => "⌫",
-            KeyboardMsg::Enter => "Enter",
+impl KeyboardMsg {
+    /// Maps a `KeyboardEvent.key` value to the matching on-screen key
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "Backspace" => Some(KeyboardMsg::Backspace),
+            "Enter" => Some(KeyboardMsg::Enter),
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) if ch.is_alphabetic() => {
+                        Some(KeyboardMsg::Letter(to_uppercase(ch)))
+                    }
+                    _ => None,
+                }
+            }
         }
     }
 }
 
 impl Display for KeyboardMsg {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s: &'static str = (*self).into();
-        write!(f, "{}", s)
+        match self {
+            KeyboardMsg::Letter(ch) => write!(f, "{ch}"),
+            KeyboardMsg::Backspace => write!(f, "⌫"),
+            KeyboardMsg::Enter => write!(f, "Enter"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum KeyboardLayout {
+    Qwerty,
+    Azerty,
+    Qwertz,
+    Dvorak,
+    Colemak,
+    Alphabetical,
+}
+
+impl KeyboardLayout {
+    pub const ALL: [KeyboardLayout; 6] = [
+        KeyboardLayout::Qwerty,
+        KeyboardLayout::Azerty,
+        KeyboardLayout::Qwertz,
+        KeyboardLayout::Dvorak,
+        KeyboardLayout::Colemak,
+        KeyboardLayout::Alphabetical,
+    ];
+
+    const STORAGE_KEY: &'static str = "keyboard_layout";
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyboardLayout::Qwerty => "QWERTY",
+            KeyboardLayout::Azerty => "AZERTY",
+            KeyboardLayout::Qwertz => "QWERTZ",
+            KeyboardLayout::Dvorak => "Dvorak",
+            KeyboardLayout::Colemak => "Colemak",
+            KeyboardLayout::Alphabetical => "Alphabetical",
+        }
+    }
+
+    /// Letter rows from top to bottom; Enter and Backspace flank the bottom row
+    fn rows(self) -> [&'static str; 3] {
+        match self {
+            KeyboardLayout::Qwerty => ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"],
+            KeyboardLayout::Azerty => ["AZERTYUIOP", "QSDFGHJKLM", "WXCVBN"],
+            KeyboardLayout::Qwertz => ["QWERTZUIOP", "ASDFGHJKL", "YXCVBNM"],
+            KeyboardLayout::Dvorak => ["PYFGCRL", "AOEUIDHTNS", "QJKXBMWVZ"],
+            KeyboardLayout::Colemak => ["QWFPGJLUY", "ARSTDHNEIO", "ZXCVBKM"],
+            KeyboardLayout::Alphabetical => ["ABCDEFGHIJ", "KLMNOPQRS", "TUVWXYZ"],
+        }
+    }
+
+    /// The layout picked on the settings page, QWERTY if none was saved
+    pub fn load() -> Self {
+        LocalStorage::get(Self::STORAGE_KEY).unwrap_or(KeyboardLayout::Qwerty)
+    }
+
+    pub fn save(self) {
+        if let Err(error) = LocalStorage::set(Self::STORAGE_KEY, self) {
+            log::error!("Could not save keyboard layout: {error}");
+        }
     }
 }
 
@@ -85,8 +120,14 @@ pub struct KeyboardProps {
     #[prop_or(true)]
     pub display: bool,
     pub callback: Callback<KeyboardMsg>,
-    #[prop_or([Correctness::Guess; 28])]
-    pub correctness_map: [Correctness; 28],
+    /// Letters missing from the map are shown as not yet guessed
+    #[prop_or_default]
+    pub correctness_map: HashMap<char, Correctness>,
+    /// Letters outside the language's alphabet are neither shown nor accepted from the keyboard
+    #[prop_or_default]
+    pub language: Language,
+    #[prop_or_else(KeyboardLayout::load)]
+    pub layout: KeyboardLayout,
 }
 
 impl Component for Keyboard {
@@ -94,13 +135,38 @@ impl Component for Keyboard {
 
     type Properties = KeyboardProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let document = gloo::utils::document();
+        let keydown_listener = EventListener::new(&document, "keydown", move |e| {
+            let e = e.dyn_ref::<KeyboardEvent>().unwrap();
+            if e.ctrl_key() || e.meta_key() || e.alt_key() || is_text_input(e) {
+                return;
+            }
+            if let Some(msg) = KeyboardMsg::from_key(&e.key()) {
+                e.prevent_default();
+                link.send_message(msg);
+            }
+        });
+        Self {
+            _keydown_listener: keydown_listener,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        let Self::Properties { callback, .. } = ctx.props();
-        callback.emit(msg);
+        let Self::Properties {
+            callback,
+            display,
+            language,
+            ..
+        } = ctx.props();
+        let accepted = match msg {
+            KeyboardMsg::Letter(ch) => language.is_letter(ch),
+            _ => true,
+        };
+        if *display && accepted {
+            callback.emit(msg);
+        }
         false
     }
 
@@ -108,53 +174,69 @@ impl Component for Keyboard {
         let Self::Properties {
             correctness_map,
             display,
+            layout,
+            language,
             ..
         } = ctx.props();
-        let mut wrapper_classes = vec![
-            "w-full",
-            "grid",
-            "grid-rows-3",
-            "gap-y-1",
-            "place-content-center",
-        ];
+        let mut wrapper_classes = vec!["w-full", "grid", "gap-y-1", "place-content-center"];
         if !display {
             wrapper_classes.push("hidden");
         }
-        html! {
-            <div class={classes!(wrapper_classes)}>
-                <div class={classes!("w-full", "grid", "grid-cols-10", "gap-x-5", "justify-items-center", "content-center")}>
+        let rows = layout.rows();
+        let [top, middle, bottom] = rows;
+        // Letters beyond A-Z, such as Ñ, get a row of their own below the layout
+        let extra: String = language
+            .alphabet()
+            .chars()
+            .filter(|&ch| !rows.iter().any(|row| row.contains(ch)))
+            .collect();
+        let render_row = |keys: Vec<KeyboardMsg>| {
+            html! {
+                <div class={classes!("w-full", "flex", "gap-x-1", "justify-center", "content-center")}>
                     {
-                        [KeyboardMsg::Q, KeyboardMsg::W, KeyboardMsg::E, KeyboardMsg::R, KeyboardMsg::T, KeyboardMsg::Y, KeyboardMsg::U, KeyboardMsg::I, KeyboardMsg::O, KeyboardMsg::P]
-                        .into_iter().map(|k| {
-                            render_key(ctx, k, correctness_map[k as usize])
+                        keys.into_iter().map(|k| {
+                            let state = match k {
+                                KeyboardMsg::Letter(ch) => correctness_map.get(&ch).copied(),
+                                _ => None,
+                            };
+                            render_key(ctx, k, state.unwrap_or(Correctness::Guess))
                         }).collect::<Html>()
                     }
                 </div>
-                <div class={classes!("w-full", "grid", "grid-cols-11", "gap-x-5", "justify-items-center", "content-center")}>
-                    <div class={classes!("h-10", "w-4", "text-white", "grid", "place-content-center")}></div>
-                    {
-                         [KeyboardMsg::A, KeyboardMsg::S, KeyboardMsg::D, KeyboardMsg::F, KeyboardMsg::G, KeyboardMsg::H, KeyboardMsg::J, KeyboardMsg::K, KeyboardMsg::L]
-                         .into_iter().map(|k| {
-                             render_key(ctx, k, correctness_map[k as usize])
-                         }).collect::<Html>()
-                    }
-                    <div class={classes!("h-10", "w-4", "text-white", "grid", "place-content-center")}></div>
-                </div>
-                <div class={classes!("w-full", "grid", "grid-cols-11", "gap-x-5", "justify-items-center", "content-center")}>
-                    <div class={classes!("h-10", "w-4", "text-white", "grid", "place-content-center")}></div>
+            }
+        };
+        html! {
+            <div class={classes!(wrapper_classes)}>
+                { render_row(letters(top).collect()) }
+                { render_row(letters(middle).collect()) }
                 {
-                    [KeyboardMsg::Enter, KeyboardMsg::Z, KeyboardMsg::X, KeyboardMsg::C, KeyboardMsg::V, KeyboardMsg::B, KeyboardMsg::N, KeyboardMsg::M, KeyboardMsg::Backspace]
-                    .into_iter().map(|k| {
-                        render_key(ctx, k, correctness_map[k as usize])
-                    }).collect::<Html>()
+                    render_row(
+                        std::iter::once(KeyboardMsg::Enter)
+                            .chain(letters(bottom))
+                            .chain(std::iter::once(KeyboardMsg::Backspace))
+                            .collect(),
+                    )
+                }
+                if !extra.is_empty() {
+                    { render_row(letters(&extra).collect()) }
                 }
-                    <div class={classes!("h-10", "w-4", "text-white", "grid", "place-content-center")}></div>
-                </div>
             </div>
         }
     }
 }
 
+fn letters(row: &str) -> impl Iterator<Item = KeyboardMsg> + '_ {
+    row.chars().map(KeyboardMsg::Letter)
+}
+
+/// Typing into a field such as the Game ID input should not also press on-screen keys
+fn is_text_input(e: &KeyboardEvent) -> bool {
+    e.target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .map(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT"))
+        .unwrap_or(false)
+}
+
 fn render_key(ctx: &Context<Keyboard>, key: KeyboardMsg, state: Correctness) -> Html {
     let mut classes = vec![
         "h-10",
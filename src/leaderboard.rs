@@ -1,12 +1,20 @@
+use std::collections::HashSet;
+
 use gloo::timers::callback::Interval;
-use reqwasm::http::Request;
-use web_sys::RequestCredentials;
+use wordle_protocol::ranking;
 #[allow(unused, dead_code)]
 use yew::{classes, html, Component, Context, Html, Properties};
+use yew_router::Routable;
 
 use crate::{
-    game_model::{self, ManageGameResponse, PlayerResponse},
+    api::{self, ApiError, EventStream, WordleApi},
+    charcell::{CharCellState, Correctness, FilledState},
+    export::ExportFormat,
+    game_model::{self, LeaderboardEvent, ManageGameResponse, PlayerResponse},
+    qr, share,
     snackbar::Snackbar,
+    wordle::Word,
+    Route,
 };
 
 #[derive(Clone, PartialEq, Properties)]
@@ -15,13 +23,32 @@ pub struct LeaderboardProps {
 }
 
 pub enum LeaderboardMsg {
-    Api(Result<ManageGameResponse, reqwasm::Error>),
+    Api(Result<ManageGameResponse, ApiError>),
+    /// `None` once the event stream has failed
+    Event(Option<LeaderboardEvent>),
+    /// Shows or hides the guess grid of the named player
+    ToggleExpanded(String),
+    ToggleHideLetters,
+    Download(ExportFormat),
+    /// Asks the server for a random answer kept from the creator
+    RevealAnswer,
+    CopyJoinLink,
+    /// Whether the join link made it to the clipboard
+    JoinLinkCopied(bool),
 }
 
 pub struct Leaderboard {
-    answer: Option<String>,
-    players: Option<Vec<PlayerResponse>>,
+    api: WordleApi,
+    game: Option<ManageGameResponse>,
     toast_msg: Option<String>,
+    /// Names of the players whose guess grids are shown
+    expanded: HashSet<String>,
+    /// Grids show colors only while someone is still playing, so spectators cannot read off
+    /// the answer
+    hide_letters: bool,
+    /// Pushes every join and guess as it happens; replaced by `poll` if it fails
+    events: Option<EventStream>,
+    poll: Option<Interval>,
 }
 
 impl Component for Leaderboard {
@@ -30,46 +57,105 @@ impl Component for Leaderboard {
     type Properties = LeaderboardProps;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let Self::Properties { game_id } = ctx.props().clone();
-        let link = ctx.link().clone();
-        Interval::new(5_000, move || {
-            let path = format!("/api/v1/manage/{}", game_id);
-            link.send_future(async move {
-                log::info!("Querying api server");
-                Self::Message::Api(
-                    match Request::get(&path)
-                        .credentials(RequestCredentials::Include)
-                        .send()
-                        .await
-                    {
-                        Ok(resp) => {
-                            log::info!("Received response: {resp:?}");
-                            resp.json::<ManageGameResponse>().await
-                        }
-                        Err(error) => Err(error),
-                    },
-                )
-            })
-        })
-        .forget();
+        let Self::Properties { game_id } = ctx.props();
+        let api = WordleApi::from_context(ctx);
+        let events = api.manage_events(game_id, ctx.link().callback(Self::Message::Event));
+        let poll = if events.is_none() {
+            Some(start_polling(ctx))
+        } else {
+            None
+        };
 
         Self {
-            answer: None,
-            players: None,
+            api,
+            game: None,
             toast_msg: None,
+            expanded: HashSet::new(),
+            hide_letters: true,
+            events,
+            poll,
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Self::Message::Api(Ok(resp)) => {
+            Self::Message::Api(Ok(resp))
+            | Self::Message::Event(Some(LeaderboardEvent::Snapshot(resp))) => {
                 log::info!("Decoded response: {resp:?}");
-                self.answer = Some(resp.answer);
-                self.players = Some(resp.players);
+                self.game = Some(resp);
+            }
+            Self::Message::Event(Some(LeaderboardEvent::PlayerJoined(player))) => {
+                if let Some(game) = &mut self.game {
+                    game.players.push(player);
+                }
+            }
+            Self::Message::Event(Some(LeaderboardEvent::Guess { name, guess })) => {
+                let player = self
+                    .game
+                    .iter_mut()
+                    .flat_map(|game| game.players.iter_mut())
+                    .find(|player| player.name == name);
+                match player {
+                    Some(player) => player.guesses.push(guess),
+                    None => log::error!("Received a guess from unknown player {name}"),
+                }
+            }
+            Self::Message::Event(None) => {
+                log::info!("Leaderboard event stream failed; polling instead");
+                self.events = None;
+                if self.poll.is_none() {
+                    self.poll = Some(start_polling(ctx));
+                }
+                return false;
+            }
+            Self::Message::ToggleExpanded(name) => {
+                if !self.expanded.remove(&name) {
+                    self.expanded.insert(name);
+                }
+            }
+            Self::Message::ToggleHideLetters => {
+                self.hide_letters = !self.hide_letters;
+            }
+            Self::Message::RevealAnswer => {
+                let api = self.api.clone();
+                let game_id = ctx.props().game_id.clone();
+                ctx.link()
+                    .send_future(async move { Self::Message::Api(api.reveal(&game_id).await) });
+                return false;
+            }
+            Self::Message::CopyJoinLink => {
+                let join_link = join_link(&ctx.props().game_id);
+                ctx.link().send_future(async move {
+                    Self::Message::JoinLinkCopied(share::copy_to_clipboard(&join_link).await)
+                });
+                return false;
+            }
+            Self::Message::JoinLinkCopied(copied) => {
+                self.toast_msg = Some(
+                    if copied {
+                        "Copied join link"
+                    } else {
+                        "Could not copy join link"
+                    }
+                    .to_owned(),
+                );
+            }
+            Self::Message::Download(format) => {
+                if let Some(game) = &self.game {
+                    format.download(&ctx.props().game_id, game);
+                }
+                return false;
             }
             Self::Message::Api(Err(error)) => {
-                log::info!("Error: {error:?}");
-                self.toast_msg = Some("An error occurred".to_owned());
+                log::info!("Error: {error}");
+                self.toast_msg = Some(
+                    match error {
+                        ApiError::NotFound => "Game not found",
+                        ApiError::Unauthorized => "Only the creator can view this leaderboard",
+                        error => error.message(),
+                    }
+                    .to_owned(),
+                );
             }
         }
         true
@@ -77,6 +163,7 @@ impl Component for Leaderboard {
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let Self::Properties { game_id } = ctx.props();
+        let join_link = join_link(game_id);
         let classes = vec![
             "w-full",
             "flex",
@@ -96,15 +183,48 @@ impl Component for Leaderboard {
                     <div class={classes}>
                         <h1>{"Leaderboard"}</h1>
                         <h2>{format!("Game id: {}", game_id)}</h2>
-                        <h3>{if let Some(answer) = &self.answer { format!("Answer: {answer}") } else { "Loading...".to_owned() }}</h3>
+                        <div class={classes!("grid", "gap-y-1", "justify-items-center", "p-2")}>
+                            { qr::qr_code(&join_link, 192) }
+                            <span class={classes!("text-xs", "break-all")}>{join_link.clone()}</span>
+                            <button onclick={ctx.link().callback(|_| Self::Message::CopyJoinLink)} class={classes!("border", "border-solid", "border-white", "rounded", "p-1")}>{"Copy join link"}</button>
+                        </div>
+                        <h3>{
+                            match &self.game {
+                                // Random answers stay hidden so the creator can play along
+                                Some(game) if game.revealable => "Answer hidden".to_owned(),
+                                // The daily leaderboard is open to everyone and keeps the answer back
+                                Some(game) if game.answer.is_empty() => "Answer hidden until you finish".to_owned(),
+                                Some(game) => format!("Answer: {}", game.answer),
+                                None => "Loading...".to_owned(),
+                            }
+                        }</h3>
+                        if self.game.as_ref().is_some_and(|game| game.revealable) {
+                            <button onclick={ctx.link().callback(|_| Self::Message::RevealAnswer)} class={classes!("border", "border-solid", "border-white", "rounded", "p-1")}>{"Reveal answer"}</button>
+                        }
+                        <label class={classes!("flex", "gap-x-2", "items-center")}>
+                            <input onchange={ctx.link().callback(|_| Self::Message::ToggleHideLetters)} type="checkbox" checked={self.hide_letters}/>
+                            {"Hide letters in grids until everyone has finished"}
+                        </label>
+                        if self.game.is_some() {
+                            <div class={classes!("flex", "gap-x-2", "p-1")}>
+                                <button onclick={ctx.link().callback(|_| Self::Message::Download(ExportFormat::Csv))} class={classes!("border", "border-solid", "border-white", "rounded", "p-1")}>{"Download CSV"}</button>
+                                <button onclick={ctx.link().callback(|_| Self::Message::Download(ExportFormat::Json))} class={classes!("border", "border-solid", "border-white", "rounded", "p-1")}>{"Download JSON"}</button>
+                            </div>
+                        }
                         {
-                            if let Some(players) = &self.players {
-                                if players.len() == 0 {
+                            if let Some(ManageGameResponse { players, max_attempts, .. }) = &self.game {
+                                if players.is_empty() {
                                     html!{"No players yet"}
                                 } else {
 
-                                players.iter().map(|player| {
-                                    let (ncorr, nincorr_pos, nincorr): (i32, i32, i32) = player.guesses.iter().map(|guess| guess.guess.iter()).flatten().fold((0,0,0), |(st_correct, st_incorrect_pos, st_incorrect), (_, correctness)|
+                                let standings = ranking::rank(players);
+                                let in_progress = standings.iter().any(|standing| !standing.solved && standing.attempts < *max_attempts);
+                                let hide_letters = self.hide_letters && in_progress;
+                                standings.into_iter().map(|standing| {
+                                    let player = standing.player;
+                                    let name = player.name.clone();
+                                    let on_player_click = ctx.link().callback(move |_| Self::Message::ToggleExpanded(name.clone()));
+                                    let (ncorr, nincorr_pos, nincorr): (i32, i32, i32) = player.guesses.iter().flat_map(|guess| guess.guess.iter()).fold((0,0,0), |(st_correct, st_incorrect_pos, st_incorrect), (_, correctness)|
                                         match correctness {
                                             game_model::Correctness::Correct => (st_correct+1, st_incorrect_pos, st_incorrect),
                                             game_model::Correctness::IncorrectPosition => (st_correct, st_incorrect_pos+1, st_incorrect),
@@ -112,20 +232,32 @@ impl Component for Leaderboard {
                                         });
 
                                     let mut classes = vec![];
-                                    if player.guesses.last().map(|guess| guess.guess.iter().all(|&(_, correctness)| correctness == game_model::Correctness::Correct)).unwrap_or(false) {
+                                    if standing.solved {
                                         classes.push("text-green-400");
                                     }
 
                                     html!{
-                                        <div class={classes!("flex", "w-full", "text-white", "justify-between")}>
-                                            <div>{player.name.clone()}</div>
-                                            <div class={classes}>{format!("{}/6", player.guesses.len())}</div>
+                                        <>
+                                        <div onclick={on_player_click} class={classes!("flex", "w-full", "text-white", "justify-between", "cursor-pointer")}>
+                                            <div>{standing.rank}</div>
+                                            <div>
+                                                {player.name.clone()}
+                                                if player.hard_mode {
+                                                    <span class={classes!("text-xs")}>{" (hard)"}</span>
+                                                }
+                                            </div>
+                                            <div class={classes}>{format!("{}/{}", standing.attempts, max_attempts)}</div>
+                                            <div>{standing.elapsed.map(format_elapsed).unwrap_or_else(|| "-".to_owned())}</div>
                                             <div class={classes!("flex", "text-white")}>
                                                 <div class={classes!("bg-green-400")}>{ncorr}</div>
                                                 <div class={classes!("bg-orange-400")}>{nincorr_pos}</div>
                                                 <div class={classes!("bg-black-400")}>{nincorr}</div>
                                             </div>
                                         </div>
+                                        if self.expanded.contains(&player.name) {
+                                            { render_guess_grid(player, hide_letters) }
+                                        }
+                                        </>
                                     }
                                 }).collect::<Html>()
                             }
@@ -140,3 +272,62 @@ impl Component for Leaderboard {
         }
     }
 }
+
+/// The player's guesses drawn like their own game board; `hide_letters` keeps only the colors
+fn render_guess_grid(player: &PlayerResponse, hide_letters: bool) -> Html {
+    html! {
+        <div class={classes!("w-full", "grid", "gap-y-1", "p-2")}>
+            {
+                player.guesses.iter().map(|guess| {
+                    let text: Vec<CharCellState> = guess.guess.iter().map(|&(ch, correctness)| {
+                        CharCellState::Filled(FilledState {
+                            ch: if hide_letters { ' ' } else { ch },
+                            correctness: Correctness::from(correctness),
+                        })
+                    }).collect();
+                    html! {
+                        <Word text={text}></Word>
+                    }
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
+
+/// Opening this registers the player for the game, see `Wordle`
+pub fn join_link(game_id: &str) -> String {
+    let origin = gloo::utils::window()
+        .location()
+        .origin()
+        .unwrap_or_default();
+    format!(
+        "{origin}{}",
+        Route::Play {
+            game_id: game_id.to_owned()
+        }
+        .to_path()
+    )
+}
+
+/// Milliseconds as `m:ss`, or `h:mm:ss` from an hour on
+fn format_elapsed(ms: u64) -> String {
+    let seconds = ms / 1000;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Polls `manage` in place of the event stream
+fn start_polling(ctx: &Context<Leaderboard>) -> Interval {
+    let game_id = ctx.props().game_id.clone();
+    api::poll(ctx, move |api| {
+        let game_id = game_id.clone();
+        async move {
+            log::info!("Querying api server");
+            LeaderboardMsg::Api(api.manage(&game_id).await)
+        }
+    })
+}
@@ -1,44 +1,34 @@
 use gloo::console::error;
-use reqwasm::http::Request;
-use web_sys::RequestCredentials;
 #[allow(unused, dead_code)]
 use yew::{classes, html, Component, Context, Html, Properties};
 
 mod answer_input;
+mod api;
 mod charcell;
-mod game_model;
+mod export;
 mod keyboard;
 mod leaderboard;
 mod menu;
+mod qr;
+mod recent_games;
 mod register;
 mod routes;
+mod session_leaderboard;
+mod settings;
+mod share;
 mod snackbar;
-mod user_model;
+mod statistics;
 mod wordle;
+mod words;
 
 use routes::*;
+use wordle_protocol::{daily, difficulty, game_model, language, session_model, user_model};
 
-async fn check_user_set() -> bool {
-    Request::get("/api/v1/user_id/verify")
-        .credentials(RequestCredentials::Include)
-        .send()
-        .await
-        .map_or_else(
-            |error| {
-                error!(format!("An error occurred verifying user id: {error:?}"));
-                false
-            },
-            |resp| {
-                if resp.ok() {
-                    true
-                } else if resp.status() == 401 {
-                    false
-                } else {
-                    error!("An error occurred on the server end: {error:?}");
-                    false
-                }
-            },
-        )
+async fn check_user_set(api: api::WordleApi) -> bool {
+    api.verify_user().await.unwrap_or_else(|error| {
+        error!(format!("An error occurred verifying user id: {error:?}"));
+        false
+    })
 }
 
 fn main() {
@@ -1,21 +1,42 @@
-use reqwasm::http::Request;
 use wasm_bindgen::JsCast;
-use web_sys::{EventTarget, HtmlInputElement, RequestCredentials};
+use web_sys::{EventTarget, HtmlInputElement};
 #[allow(unused, dead_code)]
 use yew::{classes, html, Component, Context, Html, Properties};
 use yew::{events::Event, Callback};
 use yew_router::{history::History, prelude::RouterScopeExt};
 
-use crate::{check_user_set, snackbar::Snackbar, Route};
+use crate::{
+    api::{ApiError, WordleApi},
+    check_user_set,
+    game_model::{DailyGameResponse, HistoryResponse, JoinGameRequest, PlayedHistoryResponse},
+    recent_games::{self, GameStatus, RecentGame, Role},
+    snackbar::Snackbar,
+    Route,
+};
 pub enum MenuMsg {
     Input(String),
+    HardMode(bool),
     Submit,
     VerifyUserResponse(bool),
-    SubmitResponse(Result<(), reqwasm::Error>),
+    SubmitResponse(Result<(), ApiError>),
+    History(Result<(HistoryResponse, PlayedHistoryResponse), ApiError>),
+    Daily(DailyTarget),
+    DailyResponse(DailyTarget, Result<DailyGameResponse, ApiError>),
+}
+
+/// Which page of today's shared game to open
+#[derive(Clone, Copy)]
+pub enum DailyTarget {
+    Play,
+    Leaderboard,
 }
 
 pub struct Menu {
+    api: WordleApi,
     game_id: String,
+    hard_mode: bool,
+    /// Games created or joined on this device, refreshed from the server when it can list them
+    recent_games: Vec<RecentGame>,
     verification_pending: bool,
     toast_msg: Option<String>,
 }
@@ -26,10 +47,15 @@ impl Component for Menu {
     type Properties = ();
 
     fn create(ctx: &Context<Self>) -> Self {
+        let api = WordleApi::from_context(ctx);
+        let verified = check_user_set(api.clone());
         ctx.link()
-            .send_future(async { MenuMsg::VerifyUserResponse(check_user_set().await) });
+            .send_future(async { MenuMsg::VerifyUserResponse(verified.await) });
         Self {
+            api,
             game_id: String::new(),
+            hard_mode: false,
+            recent_games: recent_games::load(),
             verification_pending: true,
             toast_msg: Some("Loading".to_owned()),
         }
@@ -44,6 +70,46 @@ impl Component for Menu {
             Self::Message::VerifyUserResponse(true) => {
                 self.verification_pending = false;
                 self.toast_msg = None;
+                let api = self.api.clone();
+                ctx.link().send_future(async move {
+                    let history = match api.history().await {
+                        Ok(created) => api.played().await.map(|played| (created, played)),
+                        Err(error) => Err(error),
+                    };
+                    Self::Message::History(history)
+                });
+                true
+            }
+            Self::Message::History(Ok((created, played))) => {
+                self.recent_games = recent_games::merge(created, played);
+                true
+            }
+            Self::Message::History(Err(error)) => {
+                log::info!("Showing locally recorded games only: {error}");
+                false
+            }
+            Self::Message::Daily(target) => {
+                let api = self.api.clone();
+                ctx.link().send_future(async move {
+                    Self::Message::DailyResponse(target, api.daily().await)
+                });
+                false
+            }
+            Self::Message::DailyResponse(target, Ok(resp)) => {
+                let game_id = resp.game_id;
+                ctx.link().history().unwrap().push(match target {
+                    DailyTarget::Play => Route::Play { game_id },
+                    DailyTarget::Leaderboard => Route::Manage { game_id },
+                });
+                false
+            }
+            Self::Message::DailyResponse(_, Err(ApiError::Unauthorized)) => {
+                ctx.link().history().unwrap().push(Route::Register);
+                false
+            }
+            Self::Message::DailyResponse(_, Err(error)) => {
+                log::error!("Could not load the daily puzzle: {error}");
+                self.toast_msg = Some(error.message().to_owned());
                 true
             }
 
@@ -51,21 +117,19 @@ impl Component for Menu {
                 self.game_id = s;
                 false
             }
+            Self::Message::HardMode(hard_mode) => {
+                self.hard_mode = hard_mode;
+                false
+            }
             Self::Message::Submit => {
                 if self.game_id.len() == 24 {
-                    let url = format!("/api/v1/game/{}/register", self.game_id);
+                    let api = self.api.clone();
+                    let game_id = self.game_id.clone();
+                    let req = JoinGameRequest {
+                        hard_mode: self.hard_mode,
+                    };
                     ctx.link().send_future(async move {
-                        Self::Message::SubmitResponse(
-                            match Request::post(&url)
-                                .header("Content-Type", "application/json")
-                                .credentials(RequestCredentials::Include)
-                                .send()
-                                .await
-                            {
-                                Ok(_) => Ok(()),
-                                Err(error) => Err(error),
-                            },
-                        )
+                        Self::Message::SubmitResponse(api.join_game(&game_id, &req).await)
                     })
                 } else {
                     self.toast_msg = Some("Game ID must be 24 characters long".to_owned());
@@ -78,34 +142,106 @@ impl Component for Menu {
                 });
                 false
             }
-            Self::Message::SubmitResponse(Err(error)) => {
-                log::error!("Received error: {error:?}");
-                self.toast_msg = Some("Error joining the game".to_owned());
+            Self::Message::SubmitResponse(Err(ApiError::Unauthorized)) => {
+                ctx.link().history().unwrap().push(Route::Register);
                 false
             }
+            Self::Message::SubmitResponse(Err(error)) => {
+                log::error!("Received error: {error}");
+                self.toast_msg = Some(
+                    match error {
+                        ApiError::NotFound => "Game not found",
+                        ApiError::Conflict => "Game already finished",
+                        _ => "Error joining the game",
+                    }
+                    .to_owned(),
+                );
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let history = ctx.link().history().clone().unwrap();
+        let history = ctx.link().history().unwrap();
         let on_create_click = Callback::once(move |_| history.push(Route::Create));
         let on_cautious_change = ctx.link().batch_callback(|e: Event| {
             let target: Option<EventTarget> = e.target();
             let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
             input.map(|input| Self::Message::Input(input.value()))
         });
+        let on_hard_mode_change = ctx.link().batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            input.map(|input| Self::Message::HardMode(input.checked()))
+        });
         let on_play_click = ctx.link().callback(|_| Self::Message::Submit);
+        let history = ctx.link().history().unwrap();
+        let on_practice_click = Callback::once(move |_| history.push(Route::Practice));
+        let history = ctx.link().history().unwrap();
+        let on_settings_click = Callback::once(move |_| history.push(Route::Settings));
+        let history = ctx.link().history().unwrap();
+        let on_stats_click = Callback::once(move |_| history.push(Route::Stats));
         html! {
             <div class={classes!("w-full", "h-full", "grid", "place-content-center")}>
                 <div class={classes!("grid", "w-80", "md:w-100", "lg:w-150",  "h-full", "gap-y-5", "text-white", "justify-items-center", "content-center")}>
                     <div class={classes!("grid", "gap-y-3")}>
                         <button onclick={on_create_click} class={classes!("border", "w-full", "border-solid", "border-white", "mb-3", "rounded")}>{"Create New Game"}</button>
                         <input onchange={on_cautious_change} class={classes!("text-black", "rounded", "p-1")} type="text" placeholder="Game ID"/>
+                        <label class={classes!("flex", "gap-x-2", "items-center")}>
+                            <input onchange={on_hard_mode_change} type="checkbox" checked={self.hard_mode}/>
+                            {"Hard mode"}
+                        </label>
                         <button onclick={on_play_click} class={classes!("border", "border-solid", "border-white", "rounded")}>{"Play"}</button>
+                        <button onclick={ctx.link().callback(|_| Self::Message::Daily(DailyTarget::Play))} class={classes!("border", "border-solid", "border-white", "rounded")}>{"Daily puzzle"}</button>
+                        <button onclick={ctx.link().callback(|_| Self::Message::Daily(DailyTarget::Leaderboard))} class={classes!("border", "border-solid", "border-white", "rounded")}>{"Daily leaderboard"}</button>
+                        <button onclick={on_practice_click} class={classes!("border", "border-solid", "border-white", "rounded")}>{"Practice"}</button>
+                        <button onclick={on_settings_click} class={classes!("border", "border-solid", "border-white", "rounded")}>{"Settings"}</button>
+                        <button onclick={on_stats_click} class={classes!("border", "border-solid", "border-white", "rounded")}>{"Statistics"}</button>
                     </div>
+                    if !self.recent_games.is_empty() {
+                        <div class={classes!("w-full", "grid", "gap-y-1")}>
+                            <div>{"Recent games"}</div>
+                            { self.recent_games.iter().map(|game| render_recent_game(ctx, game)).collect::<Html>() }
+                        </div>
+                    }
                     <Snackbar message={self.toast_msg.as_ref().cloned().unwrap_or(String::new())} display={self.toast_msg.is_some()}></Snackbar>
                 </div>
             </div>
         }
     }
 }
+
+fn render_recent_game(ctx: &Context<Menu>, game: &RecentGame) -> Html {
+    let (route, status) = match game.role {
+        Role::Created => (
+            Route::Manage {
+                game_id: game.game_id.clone(),
+            },
+            "Hosted",
+        ),
+        Role::CreatedSession => (
+            Route::Session {
+                session_id: game.game_id.clone(),
+            },
+            "Hosted session",
+        ),
+        Role::Joined => (
+            Route::Play {
+                game_id: game.game_id.clone(),
+            },
+            match game.status {
+                GameStatus::InProgress => "In progress",
+                GameStatus::Won => "Won",
+                GameStatus::Lost => "Lost",
+            },
+        ),
+    };
+    let history = ctx.link().history().unwrap();
+    let onclick = Callback::once(move |_| history.push(route));
+    html! {
+        <div {onclick} class={classes!("flex", "w-full", "justify-between", "cursor-pointer", "text-sm")}>
+            <div>{&game.game_id[game.game_id.len().saturating_sub(6)..]}</div>
+            <div>{status}</div>
+        </div>
+    }
+}
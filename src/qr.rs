@@ -0,0 +1,30 @@
+use qrcodegen::{QrCode, QrCodeEcc};
+#[allow(unused, dead_code)]
+use yew::{classes, html, Html};
+
+/// Light modules the spec requires around the code so scanners can find its edges
+const QUIET_ZONE: i32 = 4;
+
+/// `text` as a black-on-white QR code, drawn as a single SVG path that scales to any size
+pub fn qr_code(text: &str, size: u32) -> Html {
+    let qr = match QrCode::encode_text(text, QrCodeEcc::Medium) {
+        Ok(qr) => qr,
+        Err(error) => {
+            log::error!("Could not encode {text:?} as a QR code: {error}");
+            return html! {};
+        }
+    };
+    let modules = qr.size();
+    let path: String = (0..modules)
+        .flat_map(|y| (0..modules).map(move |x| (x, y)))
+        .filter(|&(x, y)| qr.get_module(x, y))
+        .map(|(x, y)| format!("M{},{}h1v1h-1z", x + QUIET_ZONE, y + QUIET_ZONE))
+        .collect();
+    let extent = modules + 2 * QUIET_ZONE;
+    html! {
+        <svg xmlns="http://www.w3.org/2000/svg" viewBox={format!("0 0 {extent} {extent}")} width={size.to_string()} height={size.to_string()} shape-rendering="crispEdges">
+            <rect width="100%" height="100%" fill="#FFFFFF"/>
+            <path d={path} fill="#000000"/>
+        </svg>
+    }
+}
@@ -0,0 +1,94 @@
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use crate::game_model::{HistoryResponse, PlayedHistoryResponse};
+
+const STORAGE_KEY: &str = "recent_games";
+/// Older games drop off the menu once there are more than this many
+const MAX_RECENT_GAMES: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Role {
+    Created,
+    Joined,
+    /// A multi-round session; `RecentGame::game_id` holds the session id
+    CreatedSession,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GameStatus {
+    InProgress,
+    Won,
+    Lost,
+}
+
+/// A game listed on the menu; a host who also plays their own game has one entry per role
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecentGame {
+    pub game_id: String,
+    pub role: Role,
+    pub status: GameStatus,
+    /// Milliseconds since the epoch of the last change, used for ordering
+    pub time: u64,
+}
+
+impl RecentGame {
+    pub fn new(game_id: &str, role: Role, status: GameStatus) -> Self {
+        Self {
+            game_id: game_id.to_owned(),
+            role,
+            status,
+            time: js_sys::Date::now() as u64,
+        }
+    }
+}
+
+/// Newest first
+pub fn load() -> Vec<RecentGame> {
+    LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+}
+
+pub fn record(game: RecentGame) {
+    let mut games = load();
+    upsert(&mut games, game);
+    save(games);
+}
+
+/// Folds in what the server knows, which wins over the local record of the same game
+pub fn merge(created: HistoryResponse, played: PlayedHistoryResponse) -> Vec<RecentGame> {
+    let mut games = load();
+    let created = created.games.into_iter().map(|game| RecentGame {
+        game_id: game.game_id,
+        role: Role::Created,
+        status: GameStatus::InProgress,
+        time: game.start_time,
+    });
+    let played = played.games.into_iter().map(|game| RecentGame {
+        game_id: game.game_id,
+        role: Role::Joined,
+        status: if game.solved {
+            GameStatus::Won
+        } else {
+            GameStatus::Lost
+        },
+        time: game.finish_time,
+    });
+    for game in created.chain(played) {
+        upsert(&mut games, game);
+    }
+    save(games.clone());
+    games
+}
+
+fn upsert(games: &mut Vec<RecentGame>, game: RecentGame) {
+    games.retain(|existing| existing.game_id != game.game_id || existing.role != game.role);
+    games.push(game);
+    games.sort_by_key(|game| std::cmp::Reverse(game.time));
+    games.truncate(MAX_RECENT_GAMES);
+}
+
+fn save(games: Vec<RecentGame>) {
+    if let Err(error) = LocalStorage::set(STORAGE_KEY, games) {
+        log::error!("Could not save recent games: {error}");
+    }
+}
@@ -1,28 +1,30 @@
-use reqwasm::http::Request;
-use web_sys::RequestCredentials;
+use yew::Callback;
 #[allow(unused, dead_code)]
 use yew::{classes, html, Component, Context, Html, Properties};
 use yew_router::{history::History, prelude::RouterScopeExt};
 
 use crate::{
+    api::{ApiError, WordleApi},
     check_user_set,
     keyboard::{Keyboard, KeyboardMsg},
+    routes::take_return_route,
     snackbar::Snackbar,
     user_model::CreateUserIdRequest,
     Route,
 };
 
-const PROMPT: &'static str = "Enter name";
+const PROMPT: &str = "Enter name";
 pub enum RegisterMsg {
     KeyboardInput(KeyboardMsg),
     VerifyUserResponse(bool),
-    RegisterUserResponse(Result<(), reqwasm::Error>),
+    RegisterUserResponse(Result<(), ApiError>),
 }
 
 #[derive(PartialEq, Properties)]
 pub struct RegisterProps;
 
 pub struct Register {
+    api: WordleApi,
     user_name: Vec<char>,
     verification_pending: bool,
     toast_msg: Option<String>,
@@ -34,9 +36,12 @@ impl Component for Register {
     type Properties = RegisterProps;
 
     fn create(ctx: &Context<Self>) -> Self {
+        let api = WordleApi::from_context(ctx);
+        let verified = check_user_set(api.clone());
         ctx.link()
-            .send_future(async { RegisterMsg::VerifyUserResponse(check_user_set().await) });
+            .send_future(async { RegisterMsg::VerifyUserResponse(verified.await) });
         Self {
+            api,
             user_name: PROMPT.chars().collect(),
             verification_pending: true,
             toast_msg: Some("Loading".to_owned()),
@@ -50,12 +55,18 @@ impl Component for Register {
                 self.keydown_handler(ctx, msg)
             }
             RegisterMsg::RegisterUserResponse(Ok(_)) => {
-                ctx.link().history().unwrap().push(Route::Menu);
+                ctx.link().history().unwrap().push(take_return_route());
                 false
             }
             RegisterMsg::RegisterUserResponse(Err(error)) => {
-                log::error!("Error registering user: {error:?}");
-                self.toast_msg = Some("Error registering user".to_owned());
+                log::error!("Error registering user: {error}");
+                self.toast_msg = Some(
+                    match error {
+                        ApiError::Conflict => "Name taken",
+                        error => error.message(),
+                    }
+                    .to_owned(),
+                );
                 true
             }
             RegisterMsg::VerifyUserResponse(false) => {
@@ -64,12 +75,14 @@ impl Component for Register {
                 true
             }
             RegisterMsg::VerifyUserResponse(true) => {
-                ctx.link().history().unwrap().push(Route::Menu);
+                ctx.link().history().unwrap().push(take_return_route());
                 false
             }
         }
     }
 
+    // yew 0.19 html! lowers `<Keyboard>`'s props builder into a bare statement
+    #[allow(clippy::unnecessary_operation)]
     fn view(&self, ctx: &Context<Self>) -> Html {
         let register_classes = vec![
             "h-80",
@@ -85,6 +98,9 @@ impl Component for Register {
             RegisterMsg::KeyboardInput(e)
         });
 
+        let history = ctx.link().history().unwrap();
+        let on_practice_click = Callback::once(move |_| history.push(Route::Practice));
+
         html! {
             <div class={classes!("w-full", "h-full", "grid", "place-content-center")}>
                 <div class={classes!("grid", "w-80", "md:w-100", "lg:w-150",  "h-full", "gap-y-5", "justify-items-center", "content-center")}>
@@ -93,6 +109,7 @@ impl Component for Register {
                         <div class={classes!("w-full", "flex", "justify-items-center", "overflow-x-auto")}>{self.user_name.iter().collect::<String>()}</div>
                     </div>
                     <Keyboard callback={onkeyclick}></Keyboard>
+                    <button onclick={on_practice_click} class={classes!("border", "border-solid", "border-white", "rounded", "text-white", "p-1")}>{"Practice offline"}</button>
                     <Snackbar message={self.toast_msg.as_ref().cloned().unwrap_or(String::new())} display={self.toast_msg.is_some()}></Snackbar>
                 </div>
             </div>
@@ -118,39 +135,22 @@ impl Register {
             }
             KeyboardMsg::Enter => {
                 if !self.user_name.is_empty() {
-                    let origin = web_sys::window().unwrap().location().origin().unwrap();
-                    log::info!("origin: {origin}");
-                    let mut api_url = url::Url::parse(&origin).unwrap();
-                    api_url.set_path("/api/v1");
-                    log::info!("api_url: {api_url:?}");
                     log::info!("Registering user name: {user_name_str}");
-                    ctx.link().send_future(async {
+                    let api = self.api.clone();
+                    ctx.link().send_future(async move {
                         let user_name = CreateUserIdRequest {
                             name: user_name_str,
                         };
                         log::info!("user_name: {user_name:?}");
-                        RegisterMsg::RegisterUserResponse(
-                            match Request::post("/api/v1/user_id")
-                                .header("Content-Type", "application/json")
-                                .body(serde_json::to_string(&user_name).unwrap())
-                                .credentials(RequestCredentials::Include)
-                                .send()
-                                .await
-                            {
-                                Ok(_resp) => Ok(()),
-                                Err(error) => Err(error),
-                            },
-                        )
+                        RegisterMsg::RegisterUserResponse(api.register_user(&user_name).await)
                     });
                 } else {
                     self.user_name = PROMPT.chars().collect();
                     return false;
                 }
             }
-            ch => {
+            KeyboardMsg::Letter(ch) => {
                 if self.user_name.len() <= 20 {
-                    let ch: &str = ch.into();
-                    let ch = ch.chars().next().unwrap();
                     self.user_name.push(ch);
                 }
             }
@@ -1,9 +1,16 @@
+// yew 0.19 html! binds the `()` props of prop-less components with `let` and lowers the
+// generic `ContextProvider`/`Switch` props builders into bare statements; the
+// `function_component` expansion drops item attributes, so the allow is module-wide.
+#![allow(clippy::let_unit_value, clippy::unnecessary_operation)]
+
 use crate::{
-    answer_input::AnswerInput, leaderboard::Leaderboard, menu::Menu, register::Register,
-    wordle::Wordle,
+    answer_input::AnswerInput, api::WordleApi, leaderboard::Leaderboard, menu::Menu,
+    register::Register, session_leaderboard::SessionLeaderboard, settings::Settings,
+    statistics::Statistics, wordle::Wordle,
 };
-use yew::{function_component, html, Html};
-use yew_router::prelude::*;
+use gloo::storage::{SessionStorage, Storage};
+use yew::{function_component, html, use_state, ContextProvider, Html};
+use yew_router::{history::AnyHistory, prelude::*};
 
 #[derive(Clone, Routable, PartialEq)]
 pub enum Route {
@@ -15,8 +22,35 @@ pub enum Route {
     Create,
     #[at("/manage/:game_id")]
     Manage { game_id: String },
+    #[at("/session/:session_id")]
+    Session { session_id: String },
     #[at("/play/:game_id")]
     Play { game_id: String },
+    #[at("/practice")]
+    Practice,
+    #[at("/settings")]
+    Settings,
+    #[at("/stats")]
+    Stats,
+}
+
+const RETURN_TO_KEY: &str = "return_to";
+
+/// Sends an unregistered user to `Route::Register`, which brings them back to `route` afterwards
+pub fn register_then_return(history: &AnyHistory, route: Route) {
+    if let Err(error) = SessionStorage::set(RETURN_TO_KEY, route.to_path()) {
+        log::error!("Could not remember the page to return to: {error}");
+    }
+    history.push(Route::Register);
+}
+
+/// Where a newly registered user should go: the page saved by `register_then_return`, or
+/// `Route::Menu`
+pub fn take_return_route() -> Route {
+    let path: Option<String> = SessionStorage::get(RETURN_TO_KEY).ok();
+    SessionStorage::delete(RETURN_TO_KEY);
+    path.and_then(|path| Route::recognize(&path))
+        .unwrap_or(Route::Menu)
 }
 
 pub fn switch(route: &Route) -> Html {
@@ -27,15 +61,27 @@ pub fn switch(route: &Route) -> Html {
         Route::Manage { game_id } => {
             html! {<Leaderboard game_id={ game_id.clone() }></Leaderboard>}
         }
-        Route::Play { game_id } => html! {<Wordle game_id={ game_id.clone() }></Wordle>},
+        Route::Session { session_id } => {
+            html! {<SessionLeaderboard session_id={ session_id.clone() }></SessionLeaderboard>}
+        }
+        // Keyed so that moving on to a session's next round starts a fresh board
+        Route::Play { game_id } => {
+            html! {<Wordle key={ game_id.clone() } game_id={ game_id.clone() }></Wordle>}
+        }
+        Route::Practice => html! {<Wordle practice=true></Wordle>},
+        Route::Settings => html! {<Settings></Settings>},
+        Route::Stats => html! {<Statistics></Statistics>},
     }
 }
 
 #[function_component(App)]
 pub fn app() -> Html {
+    let api = use_state(WordleApi::default);
     html! {
-        <BrowserRouter>
-            <Switch<Route> render={Switch::render(switch)} />
-        </BrowserRouter>
+        <ContextProvider<WordleApi> context={(*api).clone()}>
+            <BrowserRouter>
+                <Switch<Route> render={Switch::render(switch)} />
+            </BrowserRouter>
+        </ContextProvider<WordleApi>>
     }
 }
@@ -0,0 +1,166 @@
+use gloo::timers::callback::Interval;
+use wordle_protocol::ranking;
+use yew::{classes, html, Component, Context, Html, Properties};
+use yew_router::{history::History, prelude::RouterScopeExt};
+
+use crate::{
+    api::{self, ApiError},
+    leaderboard::join_link,
+    qr,
+    session_model::ManageSessionResponse,
+    snackbar::Snackbar,
+    Route,
+};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct SessionLeaderboardProps {
+    pub session_id: String,
+}
+
+pub enum SessionLeaderboardMsg {
+    Api(Result<ManageSessionResponse, ApiError>),
+    /// Opens the leaderboard of one round
+    ManageRound(String),
+}
+
+/// Cumulative scores of a multi-round session. Rounds are separate games, so there is no event
+/// stream to follow and the page simply polls.
+pub struct SessionLeaderboard {
+    session: Option<ManageSessionResponse>,
+    toast_msg: Option<String>,
+    _poll: Interval,
+}
+
+impl Component for SessionLeaderboard {
+    type Message = SessionLeaderboardMsg;
+
+    type Properties = SessionLeaderboardProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            session: None,
+            toast_msg: None,
+            _poll: start_polling(ctx),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Self::Message::Api(Ok(resp)) => {
+                self.session = Some(resp);
+                true
+            }
+            Self::Message::Api(Err(error)) => {
+                log::info!("Error: {error}");
+                self.toast_msg = Some(
+                    match error {
+                        ApiError::NotFound => "Session not found",
+                        ApiError::Unauthorized => "Only the creator can view this session",
+                        error => error.message(),
+                    }
+                    .to_owned(),
+                );
+                true
+            }
+            Self::Message::ManageRound(game_id) => {
+                ctx.link()
+                    .history()
+                    .unwrap()
+                    .push(Route::Manage { game_id });
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Self::Properties { session_id } = ctx.props();
+
+        html! {
+            <div class={classes!("w-full", "h-full", "grid", "place-content-center")}>
+                <div class={classes!("grid", "w-80", "md:w-100", "lg:w-150",  "h-full", "gap-y-5", "justify-items-center", "content-center")}>
+                    <div class={classes!("w-full", "flex", "flex-col", "flex-nowrap", "text-white", "items-center", "justify-center", "border", "border-red-400", "border-solid")}>
+                        <h1>{"Session leaderboard"}</h1>
+                        <h2>{format!("Session id: {}", session_id)}</h2>
+                        {
+                            match &self.session {
+                                Some(session) => self.render_session(ctx, session),
+                                None => html!{"Loading..."},
+                            }
+                        }
+                    </div>
+                    <Snackbar message={self.toast_msg.as_ref().cloned().unwrap_or(String::new())} display={self.toast_msg.is_some()}></Snackbar>
+                </div>
+            </div>
+        }
+    }
+}
+
+impl SessionLeaderboard {
+    fn render_session(&self, ctx: &Context<Self>, session: &ManageSessionResponse) -> Html {
+        let points = session.points;
+        let standings = ranking::rank_session(&session.rounds, &points);
+        // Players only ever need the first round; `Wordle` leads them through the rest
+        let join_link = session
+            .game_ids
+            .first()
+            .map(|game_id| join_link(game_id))
+            .unwrap_or_default();
+
+        html! {
+            <>
+                <div class={classes!("grid", "gap-y-1", "justify-items-center", "p-2")}>
+                    { qr::qr_code(&join_link, 192) }
+                    <span class={classes!("text-xs", "break-all")}>{join_link.clone()}</span>
+                </div>
+                <div class={classes!("text-xs", "p-1")}>{
+                    format!(
+                        "{} points per solved round, {} per spare attempt, {} for hard mode",
+                        points.solved, points.per_spare_attempt, points.hard_mode_bonus
+                    )
+                }</div>
+                <div class={classes!("flex", "w-full", "justify-between")}>
+                    <div class={classes!("w-10")}>{"#"}</div>
+                    <div class={classes!("flex-1")}>{"Name"}</div>
+                    {
+                        session.game_ids.iter().enumerate().map(|(round, game_id)| {
+                            let game_id = game_id.clone();
+                            let onclick = ctx.link().callback(move |_| SessionLeaderboardMsg::ManageRound(game_id.clone()));
+                            html! {
+                                <button {onclick} class={classes!("w-10", "text-right", "underline")}>{format!("R{}", round + 1)}</button>
+                            }
+                        }).collect::<Html>()
+                    }
+                    <div class={classes!("w-12", "text-right")}>{"Total"}</div>
+                </div>
+                if standings.is_empty() {
+                    {"No players yet"}
+                }
+                {
+                    standings.into_iter().map(|standing| {
+                        html! {
+                            <div class={classes!("flex", "w-full", "justify-between")}>
+                                <div class={classes!("w-10")}>{standing.rank}</div>
+                                <div class={classes!("flex-1")}>{standing.name}</div>
+                                {
+                                    standing.rounds.iter().map(|points| html! {
+                                        <div class={classes!("w-10", "text-right")}>{points.map(|points| points.to_string()).unwrap_or_else(|| "-".to_owned())}</div>
+                                    }).collect::<Html>()
+                                }
+                                <div class={classes!("w-12", "text-right")}>{standing.total}</div>
+                            </div>
+                        }
+                    }).collect::<Html>()
+                }
+            </>
+        }
+    }
+}
+
+/// Polls the session with all its rounds
+fn start_polling(ctx: &Context<SessionLeaderboard>) -> Interval {
+    let session_id = ctx.props().session_id.clone();
+    api::poll(ctx, move |api| {
+        let session_id = session_id.clone();
+        async move { SessionLeaderboardMsg::Api(api.session(&session_id).await) }
+    })
+}
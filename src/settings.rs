@@ -0,0 +1,92 @@
+use yew::{classes, html, Callback, Component, Context, Html};
+use yew_router::{history::History, prelude::RouterScopeExt};
+
+use crate::{
+    answer_input::ReusePolicy,
+    keyboard::{Keyboard, KeyboardLayout},
+    Route,
+};
+
+pub enum SettingsMsg {
+    Layout(KeyboardLayout),
+    ReusePolicy(ReusePolicy),
+}
+
+/// Device-local preferences. Nothing here is sent to the backend.
+pub struct Settings {
+    layout: KeyboardLayout,
+    reuse_policy: ReusePolicy,
+}
+
+impl Component for Settings {
+    type Message = SettingsMsg;
+
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            layout: KeyboardLayout::load(),
+            reuse_policy: ReusePolicy::load(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Self::Message::Layout(layout) => {
+                layout.save();
+                self.layout = layout;
+                true
+            }
+            Self::Message::ReusePolicy(reuse_policy) => {
+                reuse_policy.save();
+                self.reuse_policy = reuse_policy;
+                true
+            }
+        }
+    }
+
+    // yew 0.19 html! lowers `<Keyboard>`'s props builder into a bare statement
+    #[allow(clippy::unnecessary_operation)]
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let history = ctx.link().history().unwrap();
+        let on_back_click = Callback::once(move |_| history.push(Route::Menu));
+        html! {
+            <div class={classes!("w-full", "h-full", "grid", "place-content-center")}>
+                <div class={classes!("grid", "w-80", "md:w-100", "lg:w-150", "h-full", "gap-y-5", "text-white", "justify-items-center", "content-center")}>
+                    <span>{"Keyboard layout"}</span>
+                    <div class={classes!("flex", "flex-wrap", "justify-center", "gap-x-2", "gap-y-1")}>
+                        {
+                            KeyboardLayout::ALL.into_iter().map(|layout| {
+                                let selected = layout == self.layout;
+                                html! {
+                                    <button
+                                        onclick={ctx.link().callback(move |_| Self::Message::Layout(layout))}
+                                        class={classes!("border", "border-solid", "border-white", "rounded", "p-1", selected.then(|| vec!["bg-white", "text-black"]))}>
+                                        {layout.name()}
+                                    </button>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </div>
+                    <Keyboard display={true} callback={Callback::noop()} layout={self.layout}></Keyboard>
+                    <span>{"Reused answers"}</span>
+                    <div class={classes!("flex", "flex-wrap", "justify-center", "gap-x-2", "gap-y-1")}>
+                        {
+                            ReusePolicy::ALL.into_iter().map(|reuse_policy| {
+                                let selected = reuse_policy == self.reuse_policy;
+                                html! {
+                                    <button
+                                        onclick={ctx.link().callback(move |_| Self::Message::ReusePolicy(reuse_policy))}
+                                        class={classes!("border", "border-solid", "border-white", "rounded", "p-1", selected.then(|| vec!["bg-white", "text-black"]))}>
+                                        {reuse_policy.name()}
+                                    </button>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </div>
+                    <button onclick={on_back_click} class={classes!("border", "w-full", "border-solid", "border-white", "rounded")}>{"Back"}</button>
+                </div>
+            </div>
+        }
+    }
+}
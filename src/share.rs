@@ -0,0 +1,139 @@
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{HtmlDocument, HtmlTextAreaElement};
+
+use crate::{
+    charcell::{CharCellState, Correctness, FilledState},
+    game_model::DEFAULT_WORD_LENGTH,
+};
+
+/// First line of the shared result, e.g. `Game 1a2b3c 4/6*`, with `X` for an unsolved game, `*`
+/// for hard mode and the word length spelt out when it is not the default
+pub fn header(
+    name: &str,
+    solved_in: Option<usize>,
+    max_attempts: usize,
+    hard_mode: bool,
+    word_length: usize,
+) -> String {
+    let score = solved_in.map_or_else(|| "X".to_owned(), |attempts| attempts.to_string());
+    let mut header = format!("{name} {score}/{max_attempts}");
+    if hard_mode {
+        header.push('*');
+    }
+    if word_length != DEFAULT_WORD_LENGTH {
+        header.push_str(&format!(" ({word_length} letters)"));
+    }
+    header
+}
+
+/// The familiar emoji grid for a finished game, one line per guess under `header`
+pub fn result_grid(header: &str, rows: &[Vec<CharCellState>]) -> String {
+    let mut text = header.to_owned();
+    for row in rows {
+        let line: String = row
+            .iter()
+            .filter_map(|cell| match cell {
+                CharCellState::Filled(FilledState { correctness, .. }) => match correctness {
+                    Correctness::Correct => Some('🟩'),
+                    Correctness::IncorrectPosition => Some('🟨'),
+                    Correctness::Incorrect => Some('⬛'),
+                    Correctness::Guess => None,
+                },
+                CharCellState::Empty => None,
+            })
+            .collect();
+        if line.chars().count() == row.len() {
+            text.push('\n');
+            text.push_str(&line);
+        }
+    }
+    text
+}
+
+/// Tries the Clipboard API, then falls back to copying from a hidden text area for browsers
+/// or insecure origins without it. Returns whether either worked.
+pub async fn copy_to_clipboard(text: &str) -> bool {
+    match write_clipboard(text).await {
+        Ok(()) => true,
+        Err(error) => {
+            log::info!("Clipboard API unavailable, using a text area instead: {error:?}");
+            copy_with_text_area(text)
+                .map_err(|error| log::error!("Could not copy to clipboard: {error:?}"))
+                .unwrap_or(false)
+        }
+    }
+}
+
+/// `web_sys::Clipboard` is behind `web_sys_unstable_apis`, so `navigator.clipboard` is looked
+/// up by name instead
+async fn write_clipboard(text: &str) -> Result<(), JsValue> {
+    let navigator = gloo::utils::window().navigator();
+    let clipboard = Reflect::get(&navigator, &"clipboard".into())?;
+    if clipboard.is_undefined() {
+        return Err("navigator.clipboard is undefined".into());
+    }
+    let write_text: Function = Reflect::get(&clipboard, &"writeText".into())?.dyn_into()?;
+    let promise: Promise = write_text.call1(&clipboard, &text.into())?.dyn_into()?;
+    JsFuture::from(promise).await?;
+    Ok(())
+}
+
+fn copy_with_text_area(text: &str) -> Result<bool, JsValue> {
+    let document = gloo::utils::document();
+    let body = gloo::utils::body();
+    let text_area: HtmlTextAreaElement = document.create_element("textarea")?.dyn_into()?;
+    text_area.set_value(text);
+    body.append_child(&text_area)?;
+    text_area.select();
+    let copied = document.dyn_into::<HtmlDocument>()?.exec_command("copy");
+    body.remove_child(&text_area)?;
+    copied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cells: &[Correctness]) -> Vec<CharCellState> {
+        cells
+            .iter()
+            .map(|&correctness| {
+                CharCellState::Filled(FilledState {
+                    ch: 'A',
+                    correctness,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn header_shows_score_and_settings() {
+        assert_eq!(
+            header("Game 1a2b3c", Some(4), 6, false, 5),
+            "Game 1a2b3c 4/6"
+        );
+        assert_eq!(header("Practice", None, 6, false, 5), "Practice X/6");
+        assert_eq!(
+            header("Game 1a2b3c", Some(3), 8, true, 7),
+            "Game 1a2b3c 3/8* (7 letters)"
+        );
+    }
+
+    #[test]
+    fn grid_has_a_line_per_scored_guess() {
+        use Correctness::*;
+        let rows = vec![
+            row(&[Incorrect, IncorrectPosition, Incorrect, Incorrect]),
+            row(&[Correct, Correct, Correct, Correct]),
+            // Rows still being typed or never reached are left out
+            row(&[Guess, Guess, Guess, Guess]),
+            vec![CharCellState::Empty; 4],
+        ];
+        assert_eq!(
+            result_grid("Practice 2/6 (4 letters)", &rows),
+            "Practice 2/6 (4 letters)\n⬛🟨⬛⬛\n🟩🟩🟩🟩"
+        );
+    }
+}
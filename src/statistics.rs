@@ -0,0 +1,129 @@
+use gloo::storage::{LocalStorage, Storage};
+use wordle_protocol::stats::Stats;
+use yew::{classes, html, Callback, Component, Context, Html};
+use yew_router::{history::History, prelude::RouterScopeExt};
+
+use crate::{
+    api::{ApiError, WordleApi},
+    check_user_set,
+    game_model::{PlayedGame, PlayedHistoryResponse, DEFAULT_ATTEMPTS},
+    routes::register_then_return,
+    snackbar::Snackbar,
+    Route,
+};
+
+/// Finished games from the last successful fetch, so the page has something to show offline
+const CACHE_KEY: &str = "played_games";
+
+pub enum StatisticsMsg {
+    VerifyUserResponse(bool),
+    Played(Result<PlayedHistoryResponse, ApiError>),
+}
+
+pub struct Statistics {
+    api: WordleApi,
+    games: Vec<PlayedGame>,
+    toast_msg: Option<String>,
+}
+
+impl Component for Statistics {
+    type Message = StatisticsMsg;
+
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let api = WordleApi::from_context(ctx);
+        let verified = check_user_set(api.clone());
+        ctx.link()
+            .send_future(async { StatisticsMsg::VerifyUserResponse(verified.await) });
+        let games: Vec<PlayedGame> = LocalStorage::get(CACHE_KEY).unwrap_or_default();
+        Self {
+            api,
+            toast_msg: games.is_empty().then(|| "Loading".to_owned()),
+            games,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            StatisticsMsg::VerifyUserResponse(false) => {
+                register_then_return(&ctx.link().history().unwrap(), Route::Stats);
+                false
+            }
+            StatisticsMsg::VerifyUserResponse(true) => {
+                let api = self.api.clone();
+                ctx.link()
+                    .send_future(async move { StatisticsMsg::Played(api.played().await) });
+                false
+            }
+            StatisticsMsg::Played(Ok(resp)) => {
+                if let Err(error) = LocalStorage::set(CACHE_KEY, &resp.games) {
+                    log::error!("Could not cache played games: {error}");
+                }
+                self.games = resp.games;
+                self.toast_msg = None;
+                true
+            }
+            StatisticsMsg::Played(Err(error)) => {
+                log::error!("Could not load played games: {error}");
+                self.toast_msg = Some(if self.games.is_empty() {
+                    error.message().to_owned()
+                } else {
+                    "Could not refresh; showing saved statistics".to_owned()
+                });
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let stats = Stats::from_games(&self.games);
+        let rows = self
+            .games
+            .iter()
+            .map(|game| game.max_attempts)
+            .max()
+            .unwrap_or(DEFAULT_ATTEMPTS)
+            .max(DEFAULT_ATTEMPTS);
+        let most = stats.distribution.iter().copied().max().unwrap_or(0).max(1);
+        let history = ctx.link().history().unwrap();
+        let on_back_click = Callback::once(move |_| history.push(Route::Menu));
+        html! {
+            <div class={classes!("w-full", "h-full", "grid", "place-content-center")}>
+                <div class={classes!("grid", "w-80", "md:w-100", "lg:w-150", "h-full", "gap-y-5", "text-white", "justify-items-center", "content-center")}>
+                    <h1>{"Statistics"}</h1>
+                    <div class={classes!("w-full", "flex", "justify-between")}>
+                        { render_figure(stats.played, "Played") }
+                        { render_figure(stats.win_percentage(), "Win %") }
+                        { render_figure(stats.current_streak, "Current streak") }
+                        { render_figure(stats.max_streak, "Max streak") }
+                    </div>
+                    <h2>{"Guess distribution"}</h2>
+                    <div class={classes!("w-full", "grid", "gap-y-1")}>
+                        {
+                            stats.distribution.iter().take(rows).enumerate().map(|(i, &count)| {
+                                html! {
+                                    <div class={classes!("w-full", "flex", "gap-x-2")}>
+                                        <div class={classes!("w-4")}>{i + 1}</div>
+                                        <div class={classes!("bg-gray-500", "text-right", "p-1", "text-xs")} style={format!("width: {}%", 8 + 92 * count / most)}>{count}</div>
+                                    </div>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </div>
+                    <button onclick={on_back_click} class={classes!("border", "w-full", "border-solid", "border-white", "rounded")}>{"Back"}</button>
+                    <Snackbar message={self.toast_msg.as_ref().cloned().unwrap_or(String::new())} display={self.toast_msg.is_some()}></Snackbar>
+                </div>
+            </div>
+        }
+    }
+}
+
+fn render_figure(value: usize, label: &'static str) -> Html {
+    html! {
+        <div class={classes!("grid", "justify-items-center")}>
+            <div>{value}</div>
+            <div class={classes!("text-xs")}>{label}</div>
+        </div>
+    }
+}
@@ -1,15 +1,25 @@
-use crate::game_model::{self, GetStateResponse, PlayRequest};
+use crate::game_model::{
+    self, GetStateResponse, JoinGameRequest, PlayRequest, DEFAULT_ATTEMPTS, DEFAULT_WORD_LENGTH,
+};
+use std::collections::HashMap;
+
+use crate::words;
 use crate::{
+    api::{ApiError, WordleApi},
     charcell::*,
     check_user_set,
     game_model::PlayResponse,
     keyboard::{Keyboard, KeyboardMsg},
+    language::Language,
+    recent_games::{self, GameStatus, RecentGame, Role},
+    routes,
+    session_model::SessionRound,
+    share,
     snackbar::Snackbar,
     Route,
 };
 use gloo::timers::callback::Timeout;
-use reqwasm::http::Request;
-use web_sys::RequestCredentials;
+use wordle_protocol::scoring;
 #[allow(unused, dead_code)]
 use yew::{classes, html, Component, Context, Html, Properties};
 use yew_router::prelude::*;
@@ -18,6 +28,9 @@ pub struct WordProps {
     pub text: Vec<CharCellState>,
     #[prop_or(false)]
     pub animate: bool,
+    /// Shakes the row, e.g. when it holds a word that is not in the dictionary
+    #[prop_or(false)]
+    pub shake: bool,
 }
 
 pub struct Word;
@@ -32,13 +45,34 @@ impl Component for Word {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let Self::Properties { text, animate } = ctx.props();
+        let Self::Properties {
+            text,
+            animate,
+            shake,
+        } = ctx.props();
+        let mut classes = vec![
+            match text.len() {
+                4 => "grid-cols-4",
+                6 => "grid-cols-6",
+                7 => "grid-cols-7",
+                8 => "grid-cols-8",
+                _ => "grid-cols-5",
+            },
+            "grid",
+            "gap-x-1",
+            "justify-items-center",
+            "content-evenly",
+        ];
+        if *shake {
+            classes.push("animate-shake");
+        }
+        let size = CharCellSize::for_word_length(text.len());
         html! {
-            <div class={classes!("grid", "grid-cols-5", "gap-x-1", "justify-items-center", "content-evenly")}>
+            <div class={classes}>
             {
                 text.iter().enumerate().map(|(i, ccs)| {
                     html!{
-                        <CharCell state={ccs.clone()} animate={(*animate, i as u16*500)}></CharCell>
+                        <CharCell state={*ccs} animate={(*animate, i as u16*500)} size={size}></CharCell>
                     }
                 }).collect::<Html>()
             }
@@ -48,30 +82,50 @@ impl Component for Word {
 }
 
 pub enum WordleResponse {
-    GetState(Result<GetStateResponse, reqwasm::Error>),
-    PlayGame(Result<PlayResponse, reqwasm::Error>),
+    GetState(Result<GetStateResponse, ApiError>),
+    PlayGame(Result<PlayResponse, ApiError>),
 }
 pub enum WordleMsg {
     KeyboardInput(KeyboardMsg),
     VerifyUserResponse(bool),
     ApiResponse(WordleResponse),
+    Share,
+    /// Whether the result grid made it to the clipboard
+    Shared(bool),
+    /// Moves on to the next round of the session
+    NextRound,
 }
 
 #[derive(PartialEq, Properties)]
 pub struct WordleProps {
+    #[prop_or_default]
     pub game_id: String,
+    /// Plays against a bundled answer, scoring locally without the backend
+    #[prop_or(false)]
+    pub practice: bool,
 }
 
 pub struct Wordle {
+    api: WordleApi,
     animate: bool,
+    shake: bool,
     loading: bool,
     game_over: bool,
     game_id: String,
+    word_length: usize,
+    max_attempts: usize,
+    hard_mode: bool,
+    language: Language,
+    /// Scored guesses so far, kept for the hard mode check
+    guesses: Vec<Vec<(char, game_model::Correctness)>>,
     cell_i: usize,
     word_i: usize,
     verification_pending: bool,
     state: Vec<Vec<CharCellState>>,
-    correctness_map: [Correctness; 28],
+    correctness_map: HashMap<char, Correctness>,
+    practice_answer: Option<Vec<char>>,
+    /// Set when this game is one round of a session
+    session: Option<SessionRound>,
     toast_msg: Option<String>,
 }
 
@@ -81,51 +135,50 @@ impl Component for Wordle {
     type Properties = WordleProps;
 
     fn create(ctx: &Context<Self>) -> Self {
-        ctx.link()
-            .send_future(async { WordleMsg::VerifyUserResponse(check_user_set().await) });
+        let Self::Properties { game_id, practice } = ctx.props();
 
-        let Self::Properties { game_id } = ctx.props();
-
-        Self {
-            animate: false,
-            game_over: false,
-            loading: true,
-            game_id: game_id.into(),
-            cell_i: 0,
-            word_i: 0,
-            verification_pending: true,
-            state: vec![vec![CharCellState::Empty; 5]; 6],
-            correctness_map: [Correctness::Guess; 28],
-            toast_msg: Some("Loading".to_owned()),
+        let api = WordleApi::from_context(ctx);
+        if *practice {
+            let mut wordle = Self::new(api, game_id);
+            wordle.start_practice_round();
+            return wordle;
         }
+
+        let verified = check_user_set(api.clone());
+        ctx.link()
+            .send_future(async { WordleMsg::VerifyUserResponse(verified.await) });
+        Self::new(api, game_id)
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Self::Message::VerifyUserResponse(false) => {
-                ctx.link().history().unwrap().push(Route::Register);
+                self.register_then_return(ctx);
                 false
             }
             Self::Message::VerifyUserResponse(true) => {
                 self.verification_pending = false;
-                let Self::Properties { game_id } = ctx.props();
-                let url = format!("/api/v1/game/{game_id}/state");
+                let api = self.api.clone();
+                let game_id = ctx.props().game_id.clone();
                 ctx.link().send_future(async move {
-                    Self::Message::ApiResponse(WordleResponse::GetState(
-                        match Request::get(&url)
-                            .credentials(RequestCredentials::Include)
-                            .send()
-                            .await
-                        {
-                            Ok(resp) => resp.json::<GetStateResponse>().await,
-                            Err(error) => {
-                                log::error!(
-                            "Something went wrong while trying to load game state! {error:?}"
+                    // Opening a join link is enough to enter the game; a finished game is still
+                    // shown, so a conflict is not an error here
+                    match api.join_game(&game_id, &JoinGameRequest::default()).await {
+                        Ok(()) | Err(ApiError::Conflict) => {}
+                        Err(error) => {
+                            log::error!("Could not join game: {error}");
+                            return Self::Message::ApiResponse(WordleResponse::GetState(Err(
+                                error,
+                            )));
+                        }
+                    }
+                    let resp = api.get_state(&game_id).await;
+                    if let Err(error) = &resp {
+                        log::error!(
+                            "Something went wrong while trying to load game state! {error}"
                         );
-                                Err(error)
-                            }
-                        },
-                    ))
+                    }
+                    Self::Message::ApiResponse(WordleResponse::GetState(resp))
                 });
 
                 self.toast_msg = Some("Loading game state".to_owned());
@@ -133,30 +186,63 @@ impl Component for Wordle {
             }
 
             Self::Message::KeyboardInput(msg) => self.keydown_handler(ctx, msg),
+            Self::Message::Share => {
+                let text = share::result_grid(&self.share_header(), &self.state);
+                ctx.link().send_future(async move {
+                    Self::Message::Shared(share::copy_to_clipboard(&text).await)
+                });
+                false
+            }
+            // The server keeps a player who opted into hard mode in it for the rest of the
+            // session, so the next round's page joins it like any other game
+            Self::Message::NextRound => {
+                if let Some(game_id) = self.next_game_id() {
+                    ctx.link().history().unwrap().push(Route::Play { game_id });
+                }
+                false
+            }
+            Self::Message::Shared(copied) => {
+                self.toast_msg = Some(
+                    if copied {
+                        "Copied results to clipboard"
+                    } else {
+                        "Could not copy results"
+                    }
+                    .to_owned(),
+                );
+                true
+            }
             Self::Message::ApiResponse(WordleResponse::PlayGame(Ok(resp))) => {
                 log::info!("Play submitted to leaderboard");
                 log::info!("Received response: {resp:?}");
                 self.game_over = self.game_over || resp.game_over;
-                let has_won = resp.guess.into_iter().enumerate().fold(
-                    true,
-                    |all_correct, (i, (ch, correctness))| {
-                        self.state[self.word_i][i] = CharCellState::Filled(FilledState {
-                            ch,
-                            correctness: Correctness::from(correctness),
-                        });
-                        self.update_correctness_map(ch, correctness);
-                        all_correct && (correctness == game_model::Correctness::Correct)
-                    },
-                );
+                self.guesses.push(resp.guess.clone());
+                let has_won = scoring::is_solution(&resp.guess);
+                for (i, (ch, correctness)) in resp.guess.into_iter().enumerate() {
+                    self.state[self.word_i][i] = CharCellState::Filled(FilledState {
+                        ch,
+                        correctness: Correctness::from(correctness),
+                    });
+                    self.update_correctness_map(ch, correctness);
+                }
                 self.cell_i = 0;
                 self.word_i += 1;
-                if self.word_i == 6 {
+                if self.word_i == self.max_attempts {
                     self.game_over = true;
                 }
                 self.animate = true;
                 self.loading = false;
+                self.record_status(has_won);
+                let play_again = if self.practice_answer.is_some() {
+                    " Press Enter to play again."
+                } else {
+                    ""
+                };
                 if has_won {
-                    self.toast_msg = Some("You won!".to_owned());
+                    self.toast_msg = Some(format!("You won!{play_again}"));
+                } else if let (true, Some(answer)) = (self.game_over, &self.practice_answer) {
+                    let answer: String = answer.iter().collect();
+                    self.toast_msg = Some(format!("The word was {answer}.{play_again}"));
                 } else if self.game_over {
                     self.toast_msg = Some("Game over :(".to_owned());
                 }
@@ -165,16 +251,15 @@ impl Component for Wordle {
             Self::Message::ApiResponse(WordleResponse::GetState(Ok(resp))) => {
                 log::info!("Received game state response: {resp:?}");
                 self.game_over = resp.game_over;
+                self.word_length = resp.word_length;
+                self.max_attempts = resp.max_attempts;
+                self.hard_mode = resp.hard_mode;
+                self.language = resp.language;
+                self.session = resp.session;
+                self.guesses = resp.guesses.clone();
+                self.state = vec![vec![CharCellState::Empty; self.word_length]; self.max_attempts];
                 self.word_i = resp.guesses.len();
-                let has_won = resp
-                    .guesses
-                    .last()
-                    .map(|guess| {
-                        guess.iter().all(|&(_, correctness)| {
-                            correctness == game_model::Correctness::Correct
-                        })
-                    })
-                    .unwrap_or(false);
+                let has_won = scoring::solved(resp.guesses.iter().map(Vec::as_slice));
                 resp.guesses
                     .into_iter()
                     .enumerate()
@@ -192,7 +277,10 @@ impl Component for Wordle {
                     });
                 self.animate = true;
                 self.loading = false;
-                if has_won {
+                self.record_status(has_won);
+                if self.game_over && self.next_game_id().is_some() {
+                    self.toast_msg = Some("Round finished".to_owned());
+                } else if has_won {
                     self.toast_msg = Some("You won!".to_owned());
                     main_menu_timer(ctx, 10_000);
                 } else if self.game_over {
@@ -201,15 +289,44 @@ impl Component for Wordle {
                 }
                 true
             }
-            _ => {
-                log::error!("Something went wrong!");
-                self.toast_msg =
-                    Some("An error occurred; please try refreshing this page".to_owned());
+            Self::Message::ApiResponse(
+                WordleResponse::GetState(Err(ApiError::Unauthorized))
+                | WordleResponse::PlayGame(Err(ApiError::Unauthorized)),
+            ) => {
+                self.register_then_return(ctx);
+                false
+            }
+            Self::Message::ApiResponse(WordleResponse::GetState(Err(error))) => {
+                self.toast_msg = Some(
+                    match error {
+                        ApiError::NotFound => "Game not found",
+                        _ => "An error occurred; please try refreshing this page",
+                    }
+                    .to_owned(),
+                );
+                true
+            }
+            Self::Message::ApiResponse(WordleResponse::PlayGame(Err(error))) => {
+                log::error!("Could not submit guess: {error}");
+                self.loading = false;
+                self.toast_msg = Some(
+                    match error {
+                        ApiError::NotFound => "Game not found",
+                        ApiError::Conflict => {
+                            self.game_over = true;
+                            "Game already finished"
+                        }
+                        error => error.message(),
+                    }
+                    .to_owned(),
+                );
                 true
             }
         }
     }
 
+    // yew 0.19 html! lowers `<Keyboard>`'s props builder into a bare statement
+    #[allow(clippy::unnecessary_operation)]
     fn view(&self, ctx: &Context<Self>) -> Html {
         let onkeyclick = ctx.link().callback(|e: KeyboardMsg| {
             log::info!("Received KeyboardMsg: {e}");
@@ -219,16 +336,27 @@ impl Component for Wordle {
         html! {
             <div class={classes!("w-full", "h-full", "grid", "place-content-center")}>
                 <div class={classes!("grid", "w-80", "md:w-100", "lg:w-150",  "h-full", "gap-y-5", "justify-items-center", "content-center")}>
-                    <div class={classes!("h-80", "w-full", "grid", "grid-rows-6", "gap-y-1", "text-white")}>
+                    if let Some(session) = &self.session {
+                        <div class={classes!("text-white")}>{format!("Round {} of {}", session.round + 1, session.rounds)}</div>
+                    }
+                    <div class={classes!("w-full", "grid", "gap-y-1", "text-white")}>
                         {
                             self.state.iter().enumerate().map(|(i, text)| {
                                 html!{
-                                    <Word text={text.clone()} animate={i+1 == self.word_i && self.animate}></Word>
+                                    <Word text={text.clone()} animate={i+1 == self.word_i && self.animate} shake={i == self.word_i && self.shake}></Word>
                                 }
                             }).collect::<Html>()
                         }
                     </div>
-                    <Keyboard callback={onkeyclick} correctness_map={self.correctness_map}></Keyboard>
+                    if self.game_over {
+                        <div class={classes!("flex", "gap-x-2")}>
+                            <button onclick={ctx.link().callback(|_| Self::Message::Share)} class={classes!("border", "border-solid", "border-white", "rounded", "text-white", "p-1")}>{"Share"}</button>
+                            if self.next_game_id().is_some() {
+                                <button onclick={ctx.link().callback(|_| Self::Message::NextRound)} class={classes!("border", "border-solid", "border-white", "rounded", "text-white", "p-1")}>{"Next round"}</button>
+                            }
+                        </div>
+                    }
+                    <Keyboard callback={onkeyclick} correctness_map={self.correctness_map.clone()} language={self.language}></Keyboard>
                     <Snackbar message={self.toast_msg.as_ref().cloned().unwrap_or(String::new())} display={self.toast_msg.is_some()}></Snackbar>
                 </div>
             </div>
@@ -243,29 +371,126 @@ fn main_menu_timer(ctx: &Context<Wordle>, delay: u32) {
 }
 
 impl Wordle {
+    fn new(api: WordleApi, game_id: &str) -> Self {
+        Self {
+            api,
+            animate: false,
+            shake: false,
+            game_over: false,
+            loading: true,
+            game_id: game_id.into(),
+            word_length: DEFAULT_WORD_LENGTH,
+            cell_i: 0,
+            word_i: 0,
+            verification_pending: true,
+            max_attempts: DEFAULT_ATTEMPTS,
+            hard_mode: false,
+            language: Language::default(),
+            guesses: vec![],
+            state: vec![vec![CharCellState::Empty; DEFAULT_WORD_LENGTH]; DEFAULT_ATTEMPTS],
+            correctness_map: HashMap::new(),
+            practice_answer: None,
+            session: None,
+            toast_msg: Some("Loading".to_owned()),
+        }
+    }
+
+    fn start_practice_round(&mut self) {
+        *self = Self {
+            loading: false,
+            verification_pending: false,
+            practice_answer: Some(words::random_answer()),
+            toast_msg: Some("Practice mode: guesses are scored offline".to_owned()),
+            ..Self::new(self.api.clone(), "")
+        };
+    }
+
+    /// Scores the guess against the practice answer and answers as the backend would
+    fn play_practice(&self, ctx: &Context<Self>, answer: &[char], guess: Vec<char>) {
+        let correctness = scoring::score(&guess, answer);
+        let guess: Vec<_> = guess.into_iter().zip(correctness).collect();
+        let resp = PlayResponse {
+            game_over: scoring::is_solution(&guess) || self.word_i + 1 == self.max_attempts,
+            guess,
+        };
+        ctx.link()
+            .send_message(WordleMsg::ApiResponse(WordleResponse::PlayGame(Ok(resp))));
+    }
+
+    /// Keeps the menu's list of joined games up to date; practice rounds are not listed
+    fn record_status(&self, has_won: bool) {
+        if self.practice_answer.is_some() {
+            return;
+        }
+        let status = match (has_won, self.game_over) {
+            (true, _) => GameStatus::Won,
+            (false, true) => GameStatus::Lost,
+            (false, false) => GameStatus::InProgress,
+        };
+        recent_games::record(RecentGame::new(&self.game_id, Role::Joined, status));
+    }
+
+    fn next_game_id(&self) -> Option<String> {
+        self.session.as_ref()?.next_game_id.clone()
+    }
+
+    fn register_then_return(&self, ctx: &Context<Self>) {
+        let route = Route::Play {
+            game_id: self.game_id.clone(),
+        };
+        routes::register_then_return(&ctx.link().history().unwrap(), route);
+    }
+
+    /// e.g. "Game 0a1b2c 4/6* (7 letters)", where `*` marks hard mode and the length is only
+    /// given when it is not the default
+    fn share_header(&self) -> String {
+        let solved_in =
+            scoring::solved(self.guesses.iter().map(Vec::as_slice)).then_some(self.guesses.len());
+        let name = if self.practice_answer.is_some() {
+            "Practice".to_owned()
+        } else {
+            format!(
+                "Game {}",
+                &self.game_id[self.game_id.len().saturating_sub(6)..]
+            )
+        };
+        share::header(
+            &name,
+            solved_in,
+            self.max_attempts,
+            self.hard_mode,
+            self.word_length,
+        )
+    }
+
     fn update_correctness_map(&mut self, ch: char, correctness: game_model::Correctness) {
-        let ord = ch as usize - 'A' as usize;
+        let entry = self.correctness_map.entry(ch).or_insert(Correctness::Guess);
         // We do not demote correctness map for a character if it has already been set to correct anywhere
-        if self.correctness_map[ord] != Correctness::Correct {
-            if self.correctness_map[ord] == Correctness::Incorrect
-                || self.correctness_map[ord] == Correctness::Guess
-            {
-                self.correctness_map[ord] = Correctness::from(correctness);
+        if *entry != Correctness::Correct {
+            if *entry == Correctness::Incorrect || *entry == Correctness::Guess {
+                *entry = Correctness::from(correctness);
             } else {
                 // The character is already incorrect position; we should not demote it to incorrect
                 // Demotion can happen when the answer does not have repeating characters but guess
                 // has repetition
                 if correctness != game_model::Correctness::Incorrect {
-                    self.correctness_map[ord] = Correctness::from(correctness);
+                    *entry = Correctness::from(correctness);
                 }
             }
         }
     }
     fn keydown_handler(&mut self, ctx: &Context<Self>, e: KeyboardMsg) -> bool {
+        if self.game_over && self.practice_answer.is_some() {
+            if let KeyboardMsg::Enter = e {
+                self.start_practice_round();
+                return true;
+            }
+        }
         if self.game_over || self.loading {
             return false;
         }
         self.animate = false;
+        self.shake = false;
         match e {
             KeyboardMsg::Backspace => {
                 if self.cell_i > 0 {
@@ -276,7 +501,7 @@ impl Wordle {
                 }
             }
             KeyboardMsg::Enter => {
-                if self.cell_i == 5 {
+                if self.cell_i == self.word_length {
                     let guess: Vec<char> = self.state[self.word_i]
                         .iter()
                         .map(|css| match css {
@@ -284,43 +509,47 @@ impl Wordle {
                             _ => unreachable!(),
                         })
                         .collect();
-                    let url = format!("/api/v1/game/{}/play", self.game_id);
+                    if !words::is_valid_word(self.language, &guess) {
+                        self.shake = true;
+                        self.toast_msg = Some("Not in word list".to_owned());
+                        return true;
+                    }
+                    if self.hard_mode {
+                        if let Some(violation) = scoring::hard_mode_violation(&guess, &self.guesses)
+                        {
+                            self.shake = true;
+                            self.toast_msg = Some(violation.to_string());
+                            return true;
+                        }
+                    }
                     self.loading = true;
+                    if let Some(answer) = &self.practice_answer {
+                        self.play_practice(ctx, answer, guess);
+                        return false;
+                    }
+                    let api = self.api.clone();
+                    let game_id = self.game_id.clone();
                     ctx.link().send_future(async move {
                         WordleMsg::ApiResponse(WordleResponse::PlayGame(
-                            match Request::post(&url)
-                                .header("Content-Type", "application/json")
-                                .credentials(RequestCredentials::Include)
-                                .body(serde_json::to_string(&PlayRequest { guess }).unwrap())
-                                .send()
-                                .await
-                            {
-                                Ok(resp) => resp.json::<PlayResponse>().await,
-                                Err(error) => Err(error),
-                            },
+                            api.play(&game_id, &PlayRequest { guess }).await,
                         ))
                     });
-                    return false;
-                } else {
-                    return false;
                 }
+                return false;
             }
-            ch => {
-                let ch: &str = ch.into();
-                if ch.len() == 1 && self.cell_i <= 4 {
+            KeyboardMsg::Letter(ch) => {
+                if self.cell_i < self.word_length {
                     self.state[self.word_i][self.cell_i] = CharCellState::Filled(FilledState {
-                        ch: ch.chars().next().unwrap().to_ascii_uppercase(),
+                        ch,
                         correctness: Correctness::Guess,
                     });
-                    if self.cell_i <= 4 {
-                        self.cell_i += 1;
-                    }
+                    self.cell_i += 1;
                 } else {
                     return false;
                 }
             }
         }
 
-        return true;
+        true
     }
 }
@@ -0,0 +1,75 @@
+use std::sync::OnceLock;
+
+use wordle_protocol::daily::answers;
+
+use crate::language::Language;
+
+/// A practice answer from the curated list the daily puzzle also draws on
+pub fn random_answer() -> Vec<char> {
+    let count = answers().count();
+    let i = (js_sys::Math::random() * count as f64) as usize;
+    answers().nth(i.min(count - 1)).unwrap().chars().collect()
+}
+
+/// Every English word accepted as a guess, one uppercase word per line in sorted order
+const DICTIONARY: &str = include_str!("words/dictionary.txt");
+
+/// Only English has a bundled dictionary; in other languages any word spelt in the language's
+/// alphabet is accepted
+pub fn is_valid_word(language: Language, word: &[char]) -> bool {
+    if language != Language::English {
+        return word.iter().all(|&ch| language.is_letter(ch));
+    }
+    static DICTIONARY_WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    let word: String = word.iter().collect::<String>().to_uppercase();
+    DICTIONARY_WORDS
+        .get_or_init(|| DICTIONARY.lines().collect())
+        .binary_search(&word.as_str())
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use wordle_protocol::game_model::{MAX_WORD_LENGTH, MIN_WORD_LENGTH};
+
+    use super::*;
+
+    fn is_valid(language: Language, word: &str) -> bool {
+        is_valid_word(language, &word.chars().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn dictionary_is_sorted_and_in_bounds() {
+        let words: Vec<&str> = DICTIONARY.lines().collect();
+        assert!(words.windows(2).all(|w| w[0] < w[1]));
+        assert!(words.iter().all(|word| {
+            (MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&word.len())
+                && word.chars().all(|ch| Language::English.is_letter(ch))
+        }));
+    }
+
+    #[test]
+    fn accepts_common_words_in_every_language() {
+        for word in ["CRATE", "house", "WATER", "UNDER", "PEOPLE", "THOUGHT"] {
+            assert!(is_valid(Language::English, word), "{word}");
+        }
+        for word in ["CASA", "MUNDO", "NIÑOS", "AGUA", "TIEMPO"] {
+            assert!(is_valid(Language::Spanish, word), "{word}");
+        }
+        for word in ["HAUS", "UNTER", "STRAßE", "MÜDE", "SCHÖN"] {
+            assert!(is_valid(Language::German, word), "{word}");
+        }
+        for word in ["MUNDO", "AÇUCAR", "CIDADE", "CORAÇAO"] {
+            assert!(is_valid(Language::Portuguese, word), "{word}");
+        }
+    }
+
+    #[test]
+    fn rejects_words_outside_the_dictionary_or_alphabet() {
+        assert!(!is_valid(Language::English, "XQZVW"));
+        assert!(!is_valid(Language::English, "NIÑOS"));
+        assert!(!is_valid(Language::Spanish, "STRAßE"));
+        assert!(!is_valid(Language::German, "NIÑOS"));
+        assert!(!is_valid(Language::Portuguese, "MÜDE"));
+    }
+}
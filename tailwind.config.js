@@ -110,6 +110,7 @@ module.exports = {
       'card-flip-correct': 'card-flip-correct 1s ease-in-out forwards',
       'card-flip-position': 'card-flip-position 1s ease-in-out forwards',
       'card-flip-incorrect': 'card-flip-incorrect 1s ease-in-out forwards',
+      shake: 'shake 0.5s ease-in-out',
       none: 'none',
       spin: 'spin 1s linear infinite',
       ping: 'ping 1s cubic-bezier(0, 0, 0.2, 1) infinite',
@@ -607,6 +608,17 @@ module.exports = {
           'background-color': 'rgb(156, 163, 175)'
         },
       },
+      shake: {
+        '0%, 100%': {
+          transform: 'translateX(0)'
+        },
+        '20%, 60%': {
+          transform: 'translateX(-0.5rem)'
+        },
+        '40%, 80%': {
+          transform: 'translateX(0.5rem)'
+        },
+      },
       spin: {
         to: {
           transform: 'rotate(360deg)',
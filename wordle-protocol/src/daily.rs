@@ -0,0 +1,67 @@
+/// Curated answers shared by practice rounds and the daily puzzle, one uppercase word per line
+const ANSWERS: &str = include_str!("answers.txt");
+
+pub const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+pub fn answers() -> impl Iterator<Item = &'static str> {
+    ANSWERS.lines()
+}
+
+/// Days since the unix epoch in UTC, so the daily puzzle changes at the same moment everywhere
+pub fn day_of(time: u64) -> u64 {
+    time / DAY_MS
+}
+
+/// The daily puzzle's answer. Days are scrambled so consecutive days do not walk through the
+/// list in order.
+pub fn answer_for_day(day: u64) -> &'static str {
+    let count = answers().count() as u64;
+    answers().nth((scramble(day) % count) as usize).unwrap()
+}
+
+/// splitmix64's output function: spreads consecutive inputs evenly over the whole `u64` range
+pub(crate) fn scramble(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn scramble_matches_splitmix64() {
+        // The first output of the reference splitmix64 generator seeded with 0
+        assert_eq!(scramble(0), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(scramble(42), scramble(42));
+    }
+
+    #[test]
+    fn days_change_at_utc_midnight() {
+        assert_eq!(day_of(0), 0);
+        assert_eq!(day_of(DAY_MS - 1), 0);
+        assert_eq!(day_of(DAY_MS), 1);
+    }
+
+    #[test]
+    fn answer_for_day_is_deterministic_and_from_the_list() {
+        let listed: HashSet<&str> = answers().collect();
+        for day in [0, 1, 19_000, 20_000] {
+            assert_eq!(answer_for_day(day), answer_for_day(day));
+            assert!(listed.contains(answer_for_day(day)));
+        }
+    }
+
+    #[test]
+    fn neighbouring_days_get_different_answers() {
+        let year: Vec<&str> = (20_000..20_365).map(answer_for_day).collect();
+        assert!(year.windows(2).all(|days| days[0] != days[1]));
+        let distinct: HashSet<&str> = year.iter().copied().collect();
+        // Not a permutation, but a year should not keep landing on the same few words
+        assert!(distinct.len() > 300, "{} distinct answers", distinct.len());
+    }
+}
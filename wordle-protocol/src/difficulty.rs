@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::daily::{answers, scramble};
+
+/// English letters from most to least common
+const LETTER_FREQUENCY: &str = "ETAOINSHRDLCUMWFGYPBVKJXQZ";
+
+/// Tier of a curated answer, judged by how common its letters are
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+/// Rarer letters score higher, and a repeated letter counts as the rarest letter since it
+/// wastes a slot that would otherwise rule a letter in or out
+fn rarity(word: &str) -> usize {
+    let mut seen = vec![];
+    word.chars()
+        .map(|ch| {
+            if seen.contains(&ch) {
+                return LETTER_FREQUENCY.len();
+            }
+            seen.push(ch);
+            LETTER_FREQUENCY.find(ch).unwrap_or(LETTER_FREQUENCY.len())
+        })
+        .sum()
+}
+
+/// The curated answers in `difficulty`'s third of the list, or all of them for `None`
+pub fn tier(difficulty: Option<Difficulty>) -> Vec<&'static str> {
+    let mut words: Vec<&'static str> = answers().collect();
+    let Some(difficulty) = difficulty else {
+        return words;
+    };
+    words.sort_by_key(|word| rarity(word));
+    let third = words.len() / 3;
+    match difficulty {
+        Difficulty::Easy => words[..third].to_vec(),
+        Difficulty::Medium => words[third..2 * third].to_vec(),
+        Difficulty::Hard => words[2 * third..].to_vec(),
+    }
+}
+
+/// Picks an answer from `tier(difficulty)`; `seed` only needs to differ between calls
+pub fn random_answer(difficulty: Option<Difficulty>, seed: u64) -> &'static str {
+    let words = tier(difficulty);
+    words[(scramble(seed) % words.len() as u64) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn rarity_favours_common_distinct_letters() {
+        assert!(rarity("STARE") < rarity("JUMPY"));
+        // A repeat costs as much as the rarest letter
+        assert!(rarity("EERIE") > rarity("EARTH"));
+    }
+
+    #[test]
+    fn tiers_split_the_answers_into_thirds() {
+        let all = tier(None);
+        assert_eq!(all.len(), answers().count());
+        let tiers: Vec<Vec<&str>> = Difficulty::ALL.map(|d| tier(Some(d))).to_vec();
+
+        let third = all.len() / 3;
+        assert_eq!(tiers[0].len(), third);
+        assert_eq!(tiers[1].len(), third);
+        assert_eq!(tiers[2].len(), all.len() - 2 * third);
+
+        let mut seen = HashSet::new();
+        for word in tiers.iter().flatten() {
+            assert!(seen.insert(*word), "{word} is in two tiers");
+        }
+        assert_eq!(seen, all.into_iter().collect());
+
+        // Every easy word is at most as rare as every medium word, and so on
+        let max = |words: &[&str]| words.iter().map(|word| rarity(word)).max().unwrap();
+        let min = |words: &[&str]| words.iter().map(|word| rarity(word)).min().unwrap();
+        assert!(max(&tiers[0]) <= min(&tiers[1]));
+        assert!(max(&tiers[1]) <= min(&tiers[2]));
+    }
+
+    #[test]
+    fn random_answer_comes_from_the_requested_tier() {
+        for difficulty in Difficulty::ALL {
+            let words: HashSet<&str> = tier(Some(difficulty)).into_iter().collect();
+            for seed in 0..50 {
+                assert!(words.contains(random_answer(Some(difficulty), seed)));
+            }
+        }
+        let picks: HashSet<&str> = (0..50).map(|seed| random_answer(None, seed)).collect();
+        assert!(picks.len() > 40, "{} distinct picks", picks.len());
+    }
+
+    #[test]
+    fn serializes_as_lowercase_names() {
+        assert_eq!(
+            serde_json::to_string(&Difficulty::Medium).unwrap(),
+            r#""medium""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Difficulty>(r#""hard""#).unwrap(),
+            Difficulty::Hard
+        );
+    }
+}
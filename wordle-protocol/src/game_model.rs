@@ -0,0 +1,315 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    difficulty::Difficulty,
+    language::{to_uppercase, Language},
+    session_model::SessionRound,
+};
+
+pub const MIN_WORD_LENGTH: usize = 4;
+pub const MAX_WORD_LENGTH: usize = 8;
+pub const DEFAULT_WORD_LENGTH: usize = 5;
+
+pub const MIN_ATTEMPTS: usize = 3;
+pub const MAX_ATTEMPTS: usize = 8;
+pub const DEFAULT_ATTEMPTS: usize = 6;
+
+fn default_word_length() -> usize {
+    DEFAULT_WORD_LENGTH
+}
+
+fn default_max_attempts() -> usize {
+    DEFAULT_ATTEMPTS
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CreateGameRequest {
+    /// Ignored when `random_answer` is set
+    pub answer: String,
+    /// Number of letters in `answer` and in every guess, between `MIN_WORD_LENGTH` and
+    /// `MAX_WORD_LENGTH`
+    #[serde(default = "default_word_length")]
+    pub word_length: usize,
+    /// Guesses each player gets, between `MIN_ATTEMPTS` and `MAX_ATTEMPTS`
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: usize,
+    /// Forces every player to reuse revealed hints, see `scoring::hard_mode_violation`
+    #[serde(default)]
+    pub hard_mode: bool,
+    /// Alphabet the answer and every guess are spelt in
+    #[serde(default)]
+    pub language: Language,
+    /// Lets the server pick the answer from the curated English list, so the creator can play
+    /// too. Requires a five letter English game.
+    #[serde(default)]
+    pub random_answer: bool,
+    /// Narrows `random_answer` down to one tier of the list
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+}
+
+impl CreateGameRequest {
+    /// The checks the server makes before creating the game, so clients can catch mistakes
+    /// first. The error is worded for the host.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.random_answer
+            && (self.word_length != DEFAULT_WORD_LENGTH || self.language != Language::English)
+        {
+            return Err(format!(
+                "Random answers are {DEFAULT_WORD_LENGTH} letter English words"
+            ));
+        }
+        if !(MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&self.word_length) {
+            return Err(format!(
+                "Word length must be between {MIN_WORD_LENGTH} and {MAX_WORD_LENGTH}"
+            ));
+        }
+        let answer: Vec<char> = self.answer.chars().map(to_uppercase).collect();
+        if !self.random_answer
+            && (answer.len() != self.word_length
+                || !answer.iter().all(|&ch| self.language.is_letter(ch)))
+        {
+            return Err(format!("Answer must be {} letters long", self.word_length));
+        }
+        if !(MIN_ATTEMPTS..=MAX_ATTEMPTS).contains(&self.max_attempts) {
+            return Err(format!(
+                "Attempts must be between {MIN_ATTEMPTS} and {MAX_ATTEMPTS}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Body of `/game/{id}/register`; lets a player opt into hard mode for a game that does not
+/// enforce it
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct JoinGameRequest {
+    #[serde(default)]
+    pub hard_mode: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Guess {
+    pub guess: Vec<(char, Correctness)>,
+    pub submit_time: u64,
+}
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Correctness {
+    Correct,
+    IncorrectPosition,
+    Incorrect,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateGameResponse {
+    pub game_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerResponse {
+    pub name: String,
+    pub start_time: u64,
+    pub guesses: Vec<Guess>,
+    #[serde(default)]
+    pub hard_mode: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManageGameResponse {
+    pub start_time: u64,
+    pub players: Vec<PlayerResponse>,
+    pub answer: String,
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: usize,
+    #[serde(default)]
+    pub hard_mode: bool,
+    /// `answer` is empty but the caller may fetch it from `/manage/{id}/reveal`
+    #[serde(default)]
+    pub revealable: bool,
+}
+
+/// One of the caller's own games, as listed by `/history`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GameSummary {
+    pub game_id: String,
+    pub answer: String,
+    pub start_time: u64,
+    #[serde(default)]
+    pub language: Language,
+}
+
+/// Games created by the caller, newest first
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HistoryResponse {
+    pub games: Vec<GameSummary>,
+}
+
+/// A game the caller finished as a player, as listed by `/history/played`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlayedGame {
+    pub game_id: String,
+    /// When the last guess was submitted
+    pub finish_time: u64,
+    pub solved: bool,
+    pub attempts: usize,
+    pub max_attempts: usize,
+    pub word_length: usize,
+}
+
+/// Games the caller has finished playing, oldest first
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlayedHistoryResponse {
+    pub games: Vec<PlayedGame>,
+}
+
+/// The game everyone plays today, see `daily::answer_for_day`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DailyGameResponse {
+    pub game_id: String,
+    /// Days since the unix epoch in UTC
+    pub day: u64,
+}
+
+/// One message on the `/manage/{id}/events` stream
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum LeaderboardEvent {
+    /// Always the first event, so the stream alone is enough to draw the leaderboard
+    Snapshot(ManageGameResponse),
+    PlayerJoined(PlayerResponse),
+    Guess {
+        name: String,
+        guess: Guess,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PlayRequest {
+    pub guess: Vec<char>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PlayResponse {
+    pub game_over: bool,
+    pub guess: Vec<(char, Correctness)>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetStateResponse {
+    pub game_over: bool,
+    pub guesses: Vec<Vec<(char, Correctness)>>,
+    #[serde(default = "default_word_length")]
+    pub word_length: usize,
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: usize,
+    /// Whether this player's guesses are held to hard mode, by the game or by choice
+    #[serde(default)]
+    pub hard_mode: bool,
+    #[serde(default)]
+    pub language: Language,
+    /// Set when the game is one round of a multi-round session
+    #[serde(default)]
+    pub session: Option<SessionRound>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema_examples::{assert_round_trip, example};
+
+    #[test]
+    fn create_game_request_round_trips() {
+        assert_round_trip::<CreateGameRequest>(example("CreateGameRequest"));
+    }
+
+    #[test]
+    fn create_game_request_defaults() {
+        let defaults = assert_round_trip::<CreateGameRequest>(r#"{ "answer": "CRATE" }"#);
+        let req: CreateGameRequest = serde_json::from_value(defaults).unwrap();
+        assert_eq!(req.word_length, DEFAULT_WORD_LENGTH);
+        assert_eq!(req.max_attempts, DEFAULT_ATTEMPTS);
+        assert!(!req.hard_mode);
+        assert_eq!(req.language, Language::English);
+        assert!(!req.random_answer);
+        assert_eq!(req.difficulty, None);
+    }
+
+    #[test]
+    fn create_game_request_validation() {
+        let req = |json: &str| serde_json::from_str::<CreateGameRequest>(json).unwrap();
+        assert_eq!(req(r#"{ "answer": "crate" }"#).validate(), Ok(()));
+        assert_eq!(
+            req(r#"{ "answer": "piñata", "word_length": 6, "language": "es" }"#).validate(),
+            Ok(())
+        );
+        assert_eq!(
+            req(r#"{ "answer": "", "random_answer": true, "difficulty": "easy" }"#).validate(),
+            Ok(())
+        );
+        assert!(req(r#"{ "answer": "CRATES" }"#).validate().is_err());
+        assert!(req(r#"{ "answer": "PIÑAS" }"#).validate().is_err());
+        assert!(req(r#"{ "answer": "CRA", "word_length": 3 }"#)
+            .validate()
+            .is_err());
+        assert!(req(r#"{ "answer": "CRATE", "max_attempts": 9 }"#)
+            .validate()
+            .is_err());
+        assert!(
+            req(r#"{ "answer": "", "random_answer": true, "word_length": 6 }"#)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            req(r#"{ "answer": "", "random_answer": true, "language": "de" }"#)
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn join_game_request_round_trips() {
+        assert_round_trip::<JoinGameRequest>(example("JoinGameRequest"));
+        let empty = assert_round_trip::<JoinGameRequest>("{}");
+        assert_eq!(empty, serde_json::json!({ "hard_mode": false }));
+    }
+
+    #[test]
+    fn game_responses_round_trip() {
+        assert_round_trip::<CreateGameResponse>(example("CreateGameResponse"));
+        assert_round_trip::<PlayRequest>(example("PlayRequest"));
+        assert_round_trip::<PlayResponse>(example("PlayResponse"));
+        assert_round_trip::<GetStateResponse>(example("GetStateResponse"));
+        assert_round_trip::<ManageGameResponse>(example("ManageGameResponse"));
+        assert_round_trip::<HistoryResponse>(example("HistoryResponse"));
+        assert_round_trip::<PlayedHistoryResponse>(example("PlayedHistoryResponse"));
+        assert_round_trip::<DailyGameResponse>(example("DailyGameResponse"));
+    }
+
+    #[test]
+    fn older_responses_get_defaults() {
+        let state =
+            assert_round_trip::<GetStateResponse>(r#"{ "game_over": false, "guesses": [] }"#);
+        let state: GetStateResponse = serde_json::from_value(state).unwrap();
+        assert_eq!(state.word_length, DEFAULT_WORD_LENGTH);
+        assert_eq!(state.max_attempts, DEFAULT_ATTEMPTS);
+        assert_eq!(state.language, Language::English);
+        assert_eq!(state.session, None);
+
+        let game = assert_round_trip::<ManageGameResponse>(
+            r#"{ "start_time": 0, "players": [], "answer": "CRATE" }"#,
+        );
+        let game: ManageGameResponse = serde_json::from_value(game).unwrap();
+        assert_eq!(game.max_attempts, DEFAULT_ATTEMPTS);
+        assert!(!game.hard_mode && !game.revealable);
+    }
+
+    #[test]
+    fn leaderboard_events_round_trip() {
+        let events: Vec<&str> = example("LeaderboardEvent").lines().collect();
+        assert_eq!(events.len(), 3);
+        for event in events {
+            assert_round_trip::<LeaderboardEvent>(event);
+        }
+    }
+}
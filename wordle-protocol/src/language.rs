@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+/// Language of a game's answer and guesses, sent as its ISO 639-1 code
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "pt")]
+    Portuguese,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::Spanish,
+        Language::German,
+        Language::Portuguese,
+    ];
+
+    /// The language's own name for itself
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+            Language::German => "Deutsch",
+            Language::Portuguese => "Português",
+        }
+    }
+
+    /// Letters an answer or guess may use, in the form `to_uppercase` produces. Accented vowels
+    /// are written without their accents, as in the printed word games of each language.
+    pub fn alphabet(self) -> &'static str {
+        match self {
+            Language::English => "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            Language::Spanish => "ABCDEFGHIJKLMNÑOPQRSTUVWXYZ",
+            Language::German => "ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÜß",
+            Language::Portuguese => "ABCDEFGHIJKLMNOPQRSTUVWXYZÇ",
+        }
+    }
+
+    pub fn is_letter(self, ch: char) -> bool {
+        self.alphabet().contains(ch)
+    }
+}
+
+/// Uppercases a single letter, leaving letters without a one character uppercase form (such as
+/// `ß`) untouched so that words keep their length
+pub fn to_uppercase(ch: char) -> char {
+    let mut upper = ch.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => ch,
+    }
+}
@@ -0,0 +1,69 @@
+#![doc = include_str!("../README.md")]
+
+pub mod daily;
+pub mod difficulty;
+pub mod game_model;
+pub mod language;
+pub mod ranking;
+pub mod scoring;
+pub mod session_model;
+pub mod stats;
+pub mod user_model;
+
+/// Bumped whenever a wire type changes incompatibly
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Header carrying [`PROTOCOL_VERSION`] on every request sent by a client
+pub const PROTOCOL_VERSION_HEADER: &str = "X-Wordle-Protocol";
+
+/// Checks the wire types against the JSON examples in the README
+#[cfg(test)]
+pub(crate) mod schema_examples {
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::Value;
+
+    /// The JSON block following the README paragraph that starts with `` `name` ``
+    pub fn example(name: &str) -> &'static str {
+        let readme = include_str!("../README.md");
+        let heading = format!("\n`{name}`");
+        let start = readme
+            .match_indices(&heading)
+            .map(|(i, _)| i + heading.len())
+            .find(|&i| matches!(readme[i..].chars().next(), Some(':' | '\n')))
+            .unwrap_or_else(|| panic!("README has no example for {name}"));
+        let block = &readme[start..];
+        let block = &block[block.find("```json\n").unwrap() + "```json\n".len()..];
+        &block[..block.find("```").unwrap()]
+    }
+
+    /// Deserializes `json` as `T` and serializes it back. Every field in `json` must come back
+    /// unchanged; fields it leaves out must come back with their defaults.
+    pub fn assert_round_trip<T: Serialize + DeserializeOwned>(json: &str) -> Value {
+        let documented: Value = serde_json::from_str(json).unwrap();
+        let parsed: T = serde_json::from_value(documented.clone())
+            .unwrap_or_else(|error| panic!("{json} does not parse: {error}"));
+        let serialized = serde_json::to_value(&parsed).unwrap();
+        assert_contains(&serialized, &documented, "$");
+        serialized
+    }
+
+    fn assert_contains(actual: &Value, expected: &Value, path: &str) {
+        match (actual, expected) {
+            (Value::Object(actual), Value::Object(expected)) => {
+                for (key, expected) in expected {
+                    let actual = actual
+                        .get(key)
+                        .unwrap_or_else(|| panic!("{path}.{key} was dropped"));
+                    assert_contains(actual, expected, &format!("{path}.{key}"));
+                }
+            }
+            (Value::Array(actual), Value::Array(expected)) => {
+                assert_eq!(actual.len(), expected.len(), "{path} changed length");
+                for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+                    assert_contains(actual, expected, &format!("{path}[{i}]"));
+                }
+            }
+            _ => assert_eq!(actual, expected, "{path} changed"),
+        }
+    }
+}
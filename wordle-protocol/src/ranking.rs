@@ -0,0 +1,285 @@
+use crate::{
+    game_model::{ManageGameResponse, PlayerResponse},
+    scoring,
+    session_model::PointsFormula,
+};
+
+/// A player's place on the leaderboard
+#[derive(Clone, Debug)]
+pub struct Standing<'a> {
+    /// 1-based; tied players share a rank and the next rank is skipped, as in 1, 1, 3
+    pub rank: usize,
+    pub player: &'a PlayerResponse,
+    pub solved: bool,
+    pub attempts: usize,
+    /// Milliseconds from joining to the latest guess, `None` before the first guess
+    pub elapsed: Option<u64>,
+}
+
+impl Standing<'_> {
+    fn new(player: &PlayerResponse) -> Standing<'_> {
+        let solved = scoring::solved(player.guesses.iter().map(|guess| guess.guess.as_slice()));
+        Standing {
+            rank: 0,
+            player,
+            solved,
+            attempts: player.guesses.len(),
+            elapsed: player
+                .guesses
+                .last()
+                .map(|guess| guess.submit_time.saturating_sub(player.start_time)),
+        }
+    }
+
+    /// Lower sorts first: solved players, then fewer attempts, then less time
+    fn key(&self) -> (bool, usize, u64) {
+        (
+            !self.solved,
+            self.attempts,
+            self.elapsed.unwrap_or(u64::MAX),
+        )
+    }
+}
+
+/// Orders `players` best first and numbers them, keeping the server's order among ties
+pub fn rank(players: &[PlayerResponse]) -> Vec<Standing<'_>> {
+    let mut standings: Vec<Standing> = players.iter().map(Standing::new).collect();
+    standings.sort_by_key(Standing::key);
+    for i in 0..standings.len() {
+        standings[i].rank = if i > 0 && standings[i - 1].key() == standings[i].key() {
+            standings[i - 1].rank
+        } else {
+            i + 1
+        };
+    }
+    standings
+}
+
+/// A player's place on a session's cumulative leaderboard
+#[derive(Clone, Debug)]
+pub struct SessionStanding {
+    /// 1-based, with ties shared as in `rank`
+    pub rank: usize,
+    pub name: String,
+    /// Points per round, `None` for rounds the player has not joined
+    pub rounds: Vec<Option<u32>>,
+    pub total: u32,
+}
+
+/// Scores every player of every round with `points` and orders them by total, best first.
+/// Players are told apart by name, which is unique per user.
+pub fn rank_session(rounds: &[ManageGameResponse], points: &PointsFormula) -> Vec<SessionStanding> {
+    let mut standings: Vec<SessionStanding> = vec![];
+    for (round, game) in rounds.iter().enumerate() {
+        for standing in rank(&game.players) {
+            let earned = points.points(
+                standing.solved,
+                standing.attempts,
+                game.max_attempts,
+                standing.player.hard_mode,
+            );
+            let i = match standings
+                .iter()
+                .position(|existing| existing.name == standing.player.name)
+            {
+                Some(i) => i,
+                None => {
+                    standings.push(SessionStanding {
+                        rank: 0,
+                        name: standing.player.name.clone(),
+                        rounds: vec![None; rounds.len()],
+                        total: 0,
+                    });
+                    standings.len() - 1
+                }
+            };
+            standings[i].rounds[round] = Some(earned);
+            standings[i].total += earned;
+        }
+    }
+    standings.sort_by_key(|standing| std::cmp::Reverse(standing.total));
+    for i in 0..standings.len() {
+        standings[i].rank = if i > 0 && standings[i - 1].total == standings[i].total {
+            standings[i - 1].rank
+        } else {
+            i + 1
+        };
+    }
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_model::{Correctness, Guess};
+
+    /// A player who joined at 0 and guessed `attempts` times, one every `pace` ms
+    fn player(name: &str, attempts: usize, solved: bool, pace: u64) -> PlayerResponse {
+        let guesses = (1..=attempts)
+            .map(|i| {
+                let correctness = if solved && i == attempts {
+                    Correctness::Correct
+                } else {
+                    Correctness::Incorrect
+                };
+                Guess {
+                    guess: vec![('A', correctness); 5],
+                    submit_time: i as u64 * pace,
+                }
+            })
+            .collect();
+        PlayerResponse {
+            name: name.to_owned(),
+            start_time: 0,
+            guesses,
+            hard_mode: false,
+        }
+    }
+
+    fn order<'a>(standings: &[Standing<'a>]) -> Vec<(usize, &'a str)> {
+        standings
+            .iter()
+            .map(|standing| (standing.rank, standing.player.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn solved_players_come_first() {
+        let players = vec![player("lost", 2, false, 10), player("won", 6, true, 10)];
+        let standings = rank(&players);
+        assert_eq!(order(&standings), vec![(1, "won"), (2, "lost")]);
+        assert!(standings[0].solved && !standings[1].solved);
+    }
+
+    #[test]
+    fn fewer_attempts_then_less_time() {
+        let players = vec![
+            player("slow", 3, true, 20),
+            player("many", 5, true, 1),
+            player("fast", 3, true, 10),
+        ];
+        let standings = rank(&players);
+        assert_eq!(
+            order(&standings),
+            vec![(1, "fast"), (2, "slow"), (3, "many")]
+        );
+        assert_eq!(standings[0].attempts, 3);
+        assert_eq!(standings[0].elapsed, Some(30));
+    }
+
+    #[test]
+    fn ties_share_a_rank_and_skip_the_next() {
+        let players = vec![
+            player("a", 4, true, 10),
+            player("b", 4, true, 10),
+            player("c", 5, true, 10),
+        ];
+        assert_eq!(order(&rank(&players)), vec![(1, "a"), (1, "b"), (3, "c")]);
+    }
+
+    #[test]
+    fn players_without_guesses_have_no_elapsed_time() {
+        let players = vec![player("idle", 0, false, 10), player("trying", 1, false, 10)];
+        let standings = rank(&players);
+        assert_eq!(order(&standings), vec![(1, "idle"), (2, "trying")]);
+        assert_eq!(standings[0].elapsed, None);
+        assert_eq!(standings[0].attempts, 0);
+
+        // Among unsolved players fewer attempts still ranks higher, and no time ties with none
+        let players = vec![player("idle", 0, false, 10), player("also", 0, false, 10)];
+        assert_eq!(order(&rank(&players)), vec![(1, "idle"), (1, "also")]);
+    }
+
+    #[test]
+    fn ties_keep_server_order() {
+        let players = vec![
+            player("z", 2, false, 5),
+            player("m", 2, false, 5),
+            player("a", 2, false, 5),
+        ];
+        assert_eq!(order(&rank(&players)), vec![(1, "z"), (1, "m"), (1, "a")]);
+    }
+
+    fn round(players: Vec<PlayerResponse>) -> ManageGameResponse {
+        ManageGameResponse {
+            start_time: 0,
+            players,
+            answer: "CRATE".to_owned(),
+            max_attempts: 6,
+            hard_mode: false,
+            revealable: false,
+        }
+    }
+
+    fn totals(standings: &[SessionStanding]) -> Vec<(usize, &str, u32)> {
+        standings
+            .iter()
+            .map(|standing| (standing.rank, standing.name.as_str(), standing.total))
+            .collect()
+    }
+
+    const POINTS: PointsFormula = PointsFormula {
+        solved: 10,
+        per_spare_attempt: 2,
+        hard_mode_bonus: 5,
+    };
+
+    #[test]
+    fn session_merges_players_by_name_across_rounds() {
+        let mut hard = player("hard", 4, true, 10);
+        hard.hard_mode = true;
+        let rounds = vec![
+            round(vec![
+                player("early", 2, true, 10),
+                hard.clone(),
+                player("lost", 6, false, 10),
+            ]),
+            round(vec![
+                player("lost", 1, true, 10),
+                player("late", 5, true, 10),
+                hard,
+            ]),
+        ];
+        let standings = rank_session(&rounds, &POINTS);
+        assert_eq!(
+            totals(&standings),
+            vec![
+                (1, "hard", 38),
+                (2, "lost", 20),
+                (3, "early", 18),
+                (4, "late", 12)
+            ]
+        );
+        let rounds_of = |name: &str| {
+            standings
+                .iter()
+                .find(|standing| standing.name == name)
+                .unwrap()
+                .rounds
+                .clone()
+        };
+        assert_eq!(rounds_of("hard"), vec![Some(19), Some(19)]);
+        assert_eq!(rounds_of("lost"), vec![Some(0), Some(20)]);
+        // Rounds a player never joined are told apart from rounds they scored nothing in
+        assert_eq!(rounds_of("early"), vec![Some(18), None]);
+        assert_eq!(rounds_of("late"), vec![None, Some(12)]);
+    }
+
+    #[test]
+    fn session_ties_share_a_rank() {
+        let rounds = vec![
+            round(vec![player("a", 3, true, 10), player("b", 3, true, 99)]),
+            round(vec![player("c", 6, false, 10)]),
+        ];
+        assert_eq!(
+            totals(&rank_session(&rounds, &POINTS)),
+            vec![(1, "a", 16), (1, "b", 16), (3, "c", 0)]
+        );
+    }
+
+    #[test]
+    fn session_without_players() {
+        assert!(rank_session(&[round(vec![]), round(vec![])], &POINTS).is_empty());
+        assert!(rank_session(&[], &POINTS).is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+use std::fmt::Display;
+
+use crate::game_model::Correctness;
+
+/// Scores `guess` against `answer` the way the official game does.
+///
+/// Exact matches are claimed first; every other guess letter, left to right, is marked
+/// `IncorrectPosition` only while the answer still has an unclaimed copy of it. So guessing
+/// `EERIE` for `SPEED` marks the first two `E`s misplaced and the last one incorrect.
+///
+/// Both slices are expected to have the same length and the same case.
+pub fn score(guess: &[char], answer: &[char]) -> Vec<Correctness> {
+    let mut unclaimed: Vec<Option<char>> = answer
+        .iter()
+        .zip(guess)
+        .map(|(&a, &g)| if a == g { None } else { Some(a) })
+        .collect();
+    guess
+        .iter()
+        .zip(answer)
+        .map(|(&g, &a)| {
+            if g == a {
+                Correctness::Correct
+            } else if let Some(slot) = unclaimed.iter_mut().find(|slot| **slot == Some(g)) {
+                *slot = None;
+                Correctness::IncorrectPosition
+            } else {
+                Correctness::Incorrect
+            }
+        })
+        .collect()
+}
+
+/// Whether a scored guess found the answer, i.e. every letter is `Correct`
+pub fn is_solution(row: &[(char, Correctness)]) -> bool {
+    row.iter()
+        .all(|&(_, correctness)| correctness == Correctness::Correct)
+}
+
+/// Whether the last of `rows`, a player's scored guesses in order, found the answer; `false`
+/// before the first guess
+pub fn solved<'a>(rows: impl IntoIterator<Item = &'a [(char, Correctness)]>) -> bool {
+    rows.into_iter().last().is_some_and(is_solution)
+}
+
+/// A hint from an earlier guess that a hard mode guess failed to reuse
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HardModeViolation {
+    /// The letter was `Correct` at this (zero based) position and must stay there
+    MissingCorrect { position: usize, ch: char },
+    /// The letter was `IncorrectPosition` and must appear somewhere in the guess
+    MissingPresent { ch: char },
+}
+
+impl Display for HardModeViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingCorrect { position, ch } => {
+                let nth = position + 1;
+                let suffix = match (nth % 10, nth % 100) {
+                    (1, n) if n != 11 => "st",
+                    (2, n) if n != 12 => "nd",
+                    (3, n) if n != 13 => "rd",
+                    _ => "th",
+                };
+                write!(f, "{nth}{suffix} letter must be {ch}")
+            }
+            Self::MissingPresent { ch } => write!(f, "Guess must contain {ch}"),
+        }
+    }
+}
+
+/// Checks `guess` against every hint revealed by `previous` guesses: letters marked `Correct`
+/// must stay in place and letters marked `IncorrectPosition` must be used again. Position
+/// constraints are reported before missing letters.
+pub fn hard_mode_violation(
+    guess: &[char],
+    previous: &[Vec<(char, Correctness)>],
+) -> Option<HardModeViolation> {
+    let misplaced_correct = previous.iter().find_map(|row| {
+        row.iter()
+            .enumerate()
+            .find(|&(position, &(ch, correctness))| {
+                correctness == Correctness::Correct && guess.get(position) != Some(&ch)
+            })
+            .map(|(position, &(ch, _))| HardModeViolation::MissingCorrect { position, ch })
+    });
+    misplaced_correct.or_else(|| {
+        previous
+            .iter()
+            .flatten()
+            .find(|&&(ch, correctness)| {
+                correctness == Correctness::IncorrectPosition && !guess.contains(&ch)
+            })
+            .map(|&(ch, _)| HardModeViolation::MissingPresent { ch })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Correctness::{Correct, Incorrect, IncorrectPosition};
+
+    fn chars(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    fn scored(guess: &str, answer: &str) -> Vec<Correctness> {
+        score(&chars(guess), &chars(answer))
+    }
+
+    fn row(guess: &str, answer: &str) -> Vec<(char, Correctness)> {
+        chars(guess)
+            .into_iter()
+            .zip(scored(guess, answer))
+            .collect()
+    }
+
+    #[test]
+    fn all_correct() {
+        assert_eq!(scored("CRATE", "CRATE"), vec![Correct; 5]);
+    }
+
+    #[test]
+    fn all_incorrect() {
+        assert_eq!(scored("BUMPY", "CRATE"), vec![Incorrect; 5]);
+    }
+
+    #[test]
+    fn all_misplaced() {
+        assert_eq!(
+            scored("TRACE", "CRATE")[..],
+            [
+                IncorrectPosition,
+                Correct,
+                Correct,
+                IncorrectPosition,
+                Correct
+            ]
+        );
+        assert_eq!(scored("ACERT", "CRATE"), vec![IncorrectPosition; 5]);
+    }
+
+    #[test]
+    fn duplicate_in_guess_only() {
+        // CREPT has a single E, so only the first E of EERIE is marked
+        assert_eq!(
+            scored("EERIE", "CREPT"),
+            vec![
+                IncorrectPosition,
+                Incorrect,
+                IncorrectPosition,
+                Incorrect,
+                Incorrect
+            ]
+        );
+        // ...and an exact E takes it ahead of every misplaced one
+        assert_eq!(
+            scored("GEESE", "CRATE"),
+            vec![Incorrect, Incorrect, Incorrect, Incorrect, Correct]
+        );
+    }
+
+    #[test]
+    fn duplicate_in_answer_only() {
+        assert_eq!(
+            scored("CREPT", "SPEED"),
+            vec![Incorrect, Incorrect, Correct, IncorrectPosition, Incorrect]
+        );
+        assert_eq!(
+            scored("DENSE", "SPEED"),
+            vec![
+                IncorrectPosition,
+                IncorrectPosition,
+                Incorrect,
+                IncorrectPosition,
+                IncorrectPosition
+            ]
+        );
+    }
+
+    #[test]
+    fn eerie_speed_from_doc_comment() {
+        assert_eq!(
+            scored("EERIE", "SPEED"),
+            vec![
+                IncorrectPosition,
+                IncorrectPosition,
+                Incorrect,
+                Incorrect,
+                Incorrect
+            ]
+        );
+    }
+
+    #[test]
+    fn exact_match_claims_before_earlier_misplaced_copy() {
+        // The second L is exact, so the first one finds no copy left to claim
+        assert_eq!(
+            scored("LLAMA", "HELLO"),
+            vec![
+                IncorrectPosition,
+                IncorrectPosition,
+                Incorrect,
+                Incorrect,
+                Incorrect
+            ]
+        );
+        assert_eq!(
+            scored("ABBEY", "KEBAB"),
+            vec![
+                IncorrectPosition,
+                IncorrectPosition,
+                Correct,
+                IncorrectPosition,
+                Incorrect
+            ]
+        );
+        assert_eq!(
+            scored("SASSY", "BRASS"),
+            vec![
+                IncorrectPosition,
+                IncorrectPosition,
+                Incorrect,
+                Correct,
+                Incorrect
+            ]
+        );
+    }
+
+    #[test]
+    fn solved_only_looks_at_the_last_guess() {
+        let won = row("CRATE", "CRATE");
+        let lost = row("TRACE", "CRATE");
+        assert!(is_solution(&won) && !is_solution(&lost));
+        assert!(solved([lost.as_slice(), won.as_slice()]));
+        assert!(!solved([won.as_slice(), lost.as_slice()]));
+        assert!(!solved(std::iter::empty()));
+    }
+
+    #[test]
+    fn hard_mode_accepts_guess_reusing_every_hint() {
+        let previous = vec![row("TRACE", "CRATE")];
+        assert_eq!(hard_mode_violation(&chars("CRATE"), &previous), None);
+        assert_eq!(hard_mode_violation(&chars("CRATE"), &[]), None);
+    }
+
+    #[test]
+    fn hard_mode_requires_correct_letters_in_place() {
+        let previous = vec![row("TRACE", "CRATE")];
+        assert_eq!(
+            hard_mode_violation(&chars("BUMPY"), &previous),
+            Some(HardModeViolation::MissingCorrect {
+                position: 1,
+                ch: 'R'
+            })
+        );
+    }
+
+    #[test]
+    fn hard_mode_requires_misplaced_letters() {
+        let previous = vec![row("TONGS", "CRATE")];
+        assert_eq!(
+            hard_mode_violation(&chars("BUMPY"), &previous),
+            Some(HardModeViolation::MissingPresent { ch: 'T' })
+        );
+        // Anywhere will do, even the position it was already ruled out of
+        assert_eq!(hard_mode_violation(&chars("TUMPY"), &previous), None);
+    }
+
+    #[test]
+    fn hard_mode_reports_position_before_missing_letter() {
+        let previous = vec![row("TONGS", "CRATE"), row("BRAIN", "CRATE")];
+        assert_eq!(
+            hard_mode_violation(&chars("XXXXX"), &previous),
+            Some(HardModeViolation::MissingCorrect {
+                position: 1,
+                ch: 'R'
+            })
+        );
+    }
+
+    #[test]
+    fn violation_messages_use_ordinal_suffixes() {
+        let message =
+            |position| HardModeViolation::MissingCorrect { position, ch: 'A' }.to_string();
+        assert_eq!(message(0), "1st letter must be A");
+        assert_eq!(message(1), "2nd letter must be A");
+        assert_eq!(message(2), "3rd letter must be A");
+        assert_eq!(message(3), "4th letter must be A");
+        assert_eq!(message(10), "11th letter must be A");
+        assert_eq!(message(11), "12th letter must be A");
+        assert_eq!(message(12), "13th letter must be A");
+        assert_eq!(message(20), "21st letter must be A");
+        assert_eq!(
+            HardModeViolation::MissingPresent { ch: 'B' }.to_string(),
+            "Guess must contain B"
+        );
+    }
+}
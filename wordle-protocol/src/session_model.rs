@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_model::{CreateGameRequest, ManageGameResponse};
+
+pub const MIN_ROUNDS: usize = 1;
+pub const MAX_ROUNDS: usize = 5;
+
+/// How many points a player earns for one round of a session
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PointsFormula {
+    /// For solving the round at all
+    pub solved: u32,
+    /// For every attempt still left after solving
+    pub per_spare_attempt: u32,
+    /// On top of the rest for a round solved in hard mode
+    pub hard_mode_bonus: u32,
+}
+
+impl Default for PointsFormula {
+    fn default() -> Self {
+        Self {
+            solved: 10,
+            per_spare_attempt: 2,
+            hard_mode_bonus: 0,
+        }
+    }
+}
+
+impl PointsFormula {
+    /// Unsolved rounds are worth nothing
+    pub fn points(
+        &self,
+        solved: bool,
+        attempts: usize,
+        max_attempts: usize,
+        hard_mode: bool,
+    ) -> u32 {
+        if !solved {
+            return 0;
+        }
+        let spare = max_attempts.saturating_sub(attempts) as u32;
+        let bonus = if hard_mode { self.hard_mode_bonus } else { 0 };
+        self.solved + spare * self.per_spare_attempt + bonus
+    }
+}
+
+/// Body of `/session`: one game per round, played in order
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CreateSessionRequest {
+    pub rounds: Vec<CreateGameRequest>,
+    #[serde(default)]
+    pub points: PointsFormula,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateSessionResponse {
+    pub session_id: String,
+    /// The rounds' games, in order; players join through the first
+    pub game_ids: Vec<String>,
+}
+
+/// Where a game sits in its session, see `GetStateResponse::session`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionRound {
+    pub session_id: String,
+    /// 0-based
+    pub round: usize,
+    pub rounds: usize,
+    /// `None` in the last round
+    pub next_game_id: Option<String>,
+}
+
+/// The creator's view of a session, as returned by `/session/{id}`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManageSessionResponse {
+    pub game_ids: Vec<String>,
+    /// Each round's leaderboard, in the same order as `game_ids`
+    pub rounds: Vec<ManageGameResponse>,
+    pub points: PointsFormula,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema_examples::{assert_round_trip, example};
+
+    #[test]
+    fn session_types_round_trip() {
+        assert_round_trip::<CreateSessionRequest>(example("CreateSessionRequest"));
+        assert_round_trip::<CreateSessionResponse>(example("CreateSessionResponse"));
+        assert_round_trip::<ManageSessionResponse>(example("ManageSessionResponse"));
+    }
+
+    const FORMULA: PointsFormula = PointsFormula {
+        solved: 10,
+        per_spare_attempt: 2,
+        hard_mode_bonus: 5,
+    };
+
+    #[test]
+    fn unsolved_rounds_earn_nothing() {
+        assert_eq!(FORMULA.points(false, 6, 6, false), 0);
+        assert_eq!(FORMULA.points(false, 2, 6, true), 0);
+    }
+
+    #[test]
+    fn spare_attempts_add_up() {
+        assert_eq!(FORMULA.points(true, 6, 6, false), 10);
+        assert_eq!(FORMULA.points(true, 4, 6, false), 14);
+        assert_eq!(FORMULA.points(true, 1, 6, false), 20);
+        // A round that somehow took more attempts than allowed has no spares, not a penalty
+        assert_eq!(FORMULA.points(true, 7, 6, false), 10);
+    }
+
+    #[test]
+    fn hard_mode_bonus_only_for_solved_hard_rounds() {
+        assert_eq!(FORMULA.points(true, 4, 6, true), 19);
+        let no_bonus = PointsFormula {
+            hard_mode_bonus: 0,
+            ..FORMULA
+        };
+        assert_eq!(no_bonus.points(true, 4, 6, true), 14);
+    }
+
+    #[test]
+    fn points_default_to_the_documented_formula() {
+        let req = assert_round_trip::<CreateSessionRequest>(r#"{ "rounds": [] }"#);
+        let req: CreateSessionRequest = serde_json::from_value(req).unwrap();
+        let documented: CreateSessionRequest =
+            serde_json::from_str(example("CreateSessionRequest")).unwrap();
+        assert_eq!(req.points, documented.points);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::game_model::{PlayedGame, MAX_ATTEMPTS};
+
+/// A player's record across their finished games
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub played: usize,
+    pub won: usize,
+    /// Wins in a row up to and including the latest game
+    pub current_streak: usize,
+    pub max_streak: usize,
+    /// `distribution[i]` counts the games won on guess `i + 1`
+    pub distribution: [usize; MAX_ATTEMPTS],
+}
+
+impl Stats {
+    /// Games may come in any order; streaks follow `finish_time`
+    pub fn from_games(games: &[PlayedGame]) -> Self {
+        let mut games: Vec<&PlayedGame> = games.iter().collect();
+        games.sort_by_key(|game| game.finish_time);
+        let mut stats = Stats::default();
+        for game in games {
+            stats.played += 1;
+            if game.solved {
+                stats.won += 1;
+                stats.current_streak += 1;
+                stats.max_streak = stats.max_streak.max(stats.current_streak);
+                if let Some(count) = game
+                    .attempts
+                    .checked_sub(1)
+                    .and_then(|i| stats.distribution.get_mut(i))
+                {
+                    *count += 1;
+                }
+            } else {
+                stats.current_streak = 0;
+            }
+        }
+        stats
+    }
+
+    /// Rounded to the nearest whole percent, 0 before any game is played
+    pub fn win_percentage(&self) -> usize {
+        if self.played == 0 {
+            return 0;
+        }
+        (self.won * 100 + self.played / 2) / self.played
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(finish_time: u64, solved: bool, attempts: usize) -> PlayedGame {
+        PlayedGame {
+            game_id: format!("{finish_time}"),
+            finish_time,
+            solved,
+            attempts,
+            max_attempts: MAX_ATTEMPTS,
+            word_length: 5,
+        }
+    }
+
+    fn stats(results: &[(u64, bool)]) -> Stats {
+        let games: Vec<PlayedGame> = results
+            .iter()
+            .map(|&(finish_time, solved)| game(finish_time, solved, 3))
+            .collect();
+        Stats::from_games(&games)
+    }
+
+    #[test]
+    fn no_games() {
+        let stats = Stats::from_games(&[]);
+        assert_eq!(stats, Stats::default());
+        assert_eq!(stats.win_percentage(), 0);
+    }
+
+    #[test]
+    fn streaks_follow_finish_time_not_list_order() {
+        // In time order: win, win, loss, win, win, win
+        let stats = stats(&[
+            (6, true),
+            (3, false),
+            (1, true),
+            (5, true),
+            (2, true),
+            (4, true),
+        ]);
+        assert_eq!(stats.played, 6);
+        assert_eq!(stats.won, 5);
+        assert_eq!(stats.current_streak, 3);
+        assert_eq!(stats.max_streak, 3);
+    }
+
+    #[test]
+    fn loss_resets_current_streak_but_keeps_max() {
+        let recovering = stats(&[(1, true), (2, true), (3, true), (4, false), (5, true)]);
+        assert_eq!(recovering.current_streak, 1);
+        assert_eq!(recovering.max_streak, 3);
+
+        let just_lost = stats(&[(2, true), (1, true), (3, false)]);
+        assert_eq!(just_lost.current_streak, 0);
+        assert_eq!(just_lost.max_streak, 2);
+    }
+
+    #[test]
+    fn win_percentage_rounds_to_nearest() {
+        // 1/3 = 33.3%, 2/3 = 66.7%, 1/8 = 12.5%
+        assert_eq!(
+            stats(&[(1, true), (2, false), (3, false)]).win_percentage(),
+            33
+        );
+        assert_eq!(
+            stats(&[(1, true), (2, true), (3, false)]).win_percentage(),
+            67
+        );
+        let one_in_eight: Vec<(u64, bool)> = (0..8).map(|i| (i, i == 0)).collect();
+        assert_eq!(stats(&one_in_eight).win_percentage(), 13);
+        assert_eq!(stats(&[(1, true)]).win_percentage(), 100);
+        assert_eq!(stats(&[(1, false)]).win_percentage(), 0);
+    }
+
+    #[test]
+    fn distribution_counts_wins_by_attempt() {
+        let stats = Stats::from_games(&[
+            game(1, true, 1),
+            game(2, true, 3),
+            game(3, true, 3),
+            game(4, true, MAX_ATTEMPTS),
+            // Losses are not bucketed
+            game(5, false, MAX_ATTEMPTS),
+        ]);
+        let mut expected = [0; MAX_ATTEMPTS];
+        expected[0] = 1;
+        expected[2] = 2;
+        expected[MAX_ATTEMPTS - 1] = 1;
+        assert_eq!(stats.distribution, expected);
+    }
+
+    #[test]
+    fn distribution_ignores_out_of_range_attempts() {
+        let stats = Stats::from_games(&[game(1, true, 0), game(2, true, MAX_ATTEMPTS + 1)]);
+        assert_eq!(stats.distribution, [0; MAX_ATTEMPTS]);
+        // They still count as played and won
+        assert_eq!((stats.played, stats.won, stats.current_streak), (2, 2, 2));
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateUserIdRequest {
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema_examples::{assert_round_trip, example};
+
+    #[test]
+    fn create_user_id_request_round_trips() {
+        assert_round_trip::<CreateUserIdRequest>(example("CreateUserIdRequest"));
+    }
+}